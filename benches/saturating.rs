@@ -0,0 +1,54 @@
+// -*- coding: utf-8 -*-
+//
+// Copyright 2026 Michael Büsch <m@bues.ch>
+//
+// Licensed under the Apache License version 2.0
+// or the MIT license, at your option.
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//
+
+//! Feed cost of the integer overflow policies.
+//!
+//! Run with `cargo bench --bench saturating`.
+
+extern crate movavg;
+use movavg::{MovAvg, OverflowPolicy};
+use std::time::Instant;
+
+const WINDOW_SIZE: usize = 1000;
+const NR_FEEDS: usize = 200_000;
+
+fn bench(name: &str, policy: OverflowPolicy, value: i32) {
+    let mut avg: MovAvg<i32, i32, WINDOW_SIZE> = MovAvg::new().with_overflow_policy(policy);
+    // Populate the window, so that all measured feeds evict an item.
+    for _ in 0..WINDOW_SIZE {
+        avg.feed(value);
+    }
+    // Sum up the results, so that the feeds can't be optimized away.
+    let mut check: i64 = 0;
+    let start = Instant::now();
+    for i in 0..NR_FEEDS {
+        check += avg.feed(value - (i % 2) as i32) as i64;
+    }
+    let elapsed = start.elapsed();
+    println!(
+        "{:<28} {:>10.1} ns/feed (check {})",
+        name,
+        elapsed.as_nanos() as f64 / NR_FEEDS as f64,
+        check
+    );
+}
+
+fn main() {
+    println!("WINDOW_SIZE = {}", WINDOW_SIZE);
+    bench("Checked", OverflowPolicy::Checked, 1000);
+    bench("Wrapping", OverflowPolicy::Wrapping, 1000);
+    bench("Saturating, sum fits", OverflowPolicy::Saturating, 1000);
+    bench(
+        "Saturating, sum saturated",
+        OverflowPolicy::Saturating,
+        i32::MAX,
+    );
+}
+
+// vim: ts=4 sw=4 expandtab
@@ -8,6 +8,9 @@ cd "$srcdir"
 echo Running clippy...
 cargo clippy -- --deny warnings
 cargo clippy --tests -- --deny warnings
+cargo clippy --tests --features futures -- --deny warnings
+cargo clippy --tests --features serde -- --deny warnings
+cargo clippy --tests --all-features -- --deny warnings
 echo Running std tests...
 cargo test
 echo Running std/fastfloat tests...
@@ -16,3 +19,15 @@ echo Running no_std tests...
 cargo test --tests --no-default-features
 echo Running no_std/fastfloat tests...
 cargo test --tests --no-default-features --features fastfloat
+echo Running no_std/alloc tests...
+cargo test --tests --no-default-features --features alloc
+echo Running std/futures tests...
+cargo test --features futures
+echo Running no_std/futures tests...
+cargo test --tests --no-default-features --features futures
+echo Running std/serde tests...
+cargo test --features serde
+echo Running no_std/serde tests...
+cargo test --tests --no-default-features --features serde
+echo Running all-features tests...
+cargo test --all-features
@@ -0,0 +1,432 @@
+// -*- coding: utf-8 -*-
+//
+// Copyright 2026 Michael Büsch <m@bues.ch>
+//
+// Licensed under the Apache License version 2.0
+// or the MIT license, at your option.
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//
+
+use crate::error::MovAvgError;
+use core::marker::PhantomData;
+use num_traits::{Num, NumCast};
+
+/// Internal EMA calculation trait for integers and floats.
+///
+/// This usually does *not* have to be implemented by the library user.
+/// The `movavg` crate implements this trait for all core integers and floats.
+///
+/// `Self` is the accumulator type `A`.
+///
+/// Integer accumulators store the smoothing factor and the average
+/// as fixed-point numbers with `A::BITS / 4` fractional bits.
+/// Float accumulators store both values as-is.
+pub trait EmaAccu: Copy {
+    /// Convert the smoothing factor `alpha_num / alpha_den`
+    /// into the internal representation.
+    fn ema_alpha(alpha_num: usize, alpha_den: usize) -> Result<Self, MovAvgError>;
+
+    /// Convert the first input value into the internal representation.
+    fn ema_init(input_value: Self) -> Result<Self, MovAvgError>;
+
+    /// Move the internal average towards `input_value` by the factor `alpha`.
+    fn ema_update(self, alpha: Self, input_value: Self) -> Result<Self, MovAvgError>;
+
+    /// Convert the internal representation back into an average value.
+    fn ema_value(self) -> Self;
+}
+
+/// Check the smoothing factor fraction for `0 < alpha <= 1`.
+#[inline]
+fn check_alpha(alpha_num: usize, alpha_den: usize) -> Result<(), MovAvgError> {
+    if alpha_num == 0 || alpha_num > alpha_den {
+        Err(MovAvgError::InvalidAlpha)
+    } else {
+        Ok(())
+    }
+}
+
+macro_rules! impl_int_ema_accu {
+    ($($t:ty),*) => {
+        $(
+            impl EmaAccu for $t {
+                #[inline]
+                fn ema_alpha(alpha_num: usize, alpha_den: usize) -> Result<Self, MovAvgError> {
+                    const FRAC: u32 = <$t>::BITS / 4;
+                    check_alpha(alpha_num, alpha_den)?;
+                    let alpha = ((alpha_num as u128) << FRAC) / alpha_den as u128;
+                    match <$t>::try_from(alpha) {
+                        Ok(alpha) if alpha > 0 => Ok(alpha),
+                        _ => Err(MovAvgError::InvalidAlpha),
+                    }
+                }
+
+                #[inline]
+                fn ema_init(input_value: Self) -> Result<Self, MovAvgError> {
+                    const ONE: $t = 1 << (<$t>::BITS / 4);
+                    input_value.checked_mul(ONE)
+                        .ok_or(MovAvgError::AccuOverflow)
+                }
+
+                #[inline]
+                fn ema_update(self, alpha: Self, input_value: Self) -> Result<Self, MovAvgError> {
+                    const ONE: $t = 1 << (<$t>::BITS / 4);
+                    let input_value = input_value.checked_mul(ONE)
+                        .ok_or(MovAvgError::AccuOverflow)?;
+                    // Split the difference into integer and fractional part,
+                    // so that the multiplication with alpha (<= ONE) can't overflow.
+                    let step = |diff: $t| (diff / ONE) * alpha + ((diff % ONE) * alpha) / ONE;
+                    if input_value >= self {
+                        let diff = input_value.checked_sub(self)
+                            .ok_or(MovAvgError::AccuOverflow)?;
+                        Ok(self + step(diff))
+                    } else {
+                        let diff = self.checked_sub(input_value)
+                            .ok_or(MovAvgError::AccuOverflow)?;
+                        Ok(self - step(diff))
+                    }
+                }
+
+                #[inline]
+                fn ema_value(self) -> Self {
+                    const ONE: $t = 1 << (<$t>::BITS / 4);
+                    self / ONE
+                }
+            }
+        )*
+    }
+}
+
+macro_rules! impl_float_ema_accu {
+    ($($t:ty),*) => {
+        $(
+            impl EmaAccu for $t {
+                #[inline]
+                fn ema_alpha(alpha_num: usize, alpha_den: usize) -> Result<Self, MovAvgError> {
+                    check_alpha(alpha_num, alpha_den)?;
+                    Ok(alpha_num as $t / alpha_den as $t)
+                }
+
+                #[inline]
+                fn ema_init(input_value: Self) -> Result<Self, MovAvgError> {
+                    Ok(input_value)
+                }
+
+                #[inline]
+                fn ema_update(self, alpha: Self, input_value: Self) -> Result<Self, MovAvgError> {
+                    Ok(self + alpha * (input_value - self))
+                }
+
+                #[inline]
+                fn ema_value(self) -> Self {
+                    self
+                }
+            }
+        )*
+    }
+}
+
+impl_int_ema_accu!(i8, i16, i32, i64, isize, u8, u16, u32, u64, usize);
+
+#[cfg(has_i128)]
+impl_int_ema_accu!(i128, u128);
+
+impl_float_ema_accu!(f32, f64);
+
+/// Exponential Moving Average (EMA)
+///
+/// Each fed value moves the average towards the value by the smoothing factor `alpha`:
+///
+/// `avg = avg + alpha * (value - avg)`
+///
+/// The first fed value initializes the average.
+///
+/// # Examples
+///
+/// ```
+/// use movavg::Ema;
+///
+/// let mut avg: Ema<f64, f64> = Ema::new(1, 2); // alpha = 1/2
+/// assert_eq!(avg.feed(10.0), 10.0);
+/// assert_eq!(avg.feed(20.0), 15.0);
+/// assert_eq!(avg.feed(20.0), 17.5);
+/// assert_eq!(avg.get(), 17.5);
+///
+/// let mut avg: Ema<i32, i64> = Ema::new(1, 2);
+/// assert_eq!(avg.feed(10), 10);
+/// assert_eq!(avg.feed(20), 15);
+/// assert_eq!(avg.feed(20), 17);
+/// ```
+///
+/// # Type Generics
+///
+/// `struct Ema<T, A>`
+///
+/// * `T` - The type of the `feed()` input value.
+/// * `A` - The type of the internal accumulator.
+///   This type must be bigger then or equal to `T`.
+///   Integer accumulators use fixed-point arithmetic with `A::BITS / 4` fractional bits.
+///   Therefore, the accumulator must have at least `A::BITS / 4 + 1` bits more
+///   than needed to represent the input values.
+#[derive(Clone, Debug)]
+pub struct Ema<T, A> {
+    alpha: A,
+    accu: A,
+    populated: bool,
+    _marker: PhantomData<T>,
+}
+
+impl<T, A> Ema<T, A>
+where
+    T: Num + NumCast + Copy,
+    A: Num + NumCast + Copy + EmaAccu,
+{
+    /// Construct a new Exponential Moving Average with the smoothing factor
+    /// `alpha = alpha_num / alpha_den`.
+    ///
+    /// A bigger `alpha` discounts older values faster.
+    ///
+    /// # Panics
+    ///
+    /// Panics, if:
+    /// * `alpha` is not in the range `0 < alpha <= 1`.
+    /// * `alpha` is too small to be represented by the accumulator type.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use movavg::Ema;
+    ///
+    /// let mut avg: Ema<i32, i64> = Ema::new(1, 4); // alpha = 0.25
+    /// assert_eq!(avg.feed(100), 100);
+    /// assert_eq!(avg.feed(200), 125);
+    /// ```
+    pub fn new(alpha_num: usize, alpha_den: usize) -> Ema<T, A> {
+        let alpha = A::ema_alpha(alpha_num, alpha_den).expect("Invalid smoothing factor.");
+        Ema {
+            alpha,
+            accu: A::zero(),
+            populated: false,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Construct a new Exponential Moving Average with a smoothing factor
+    /// that corresponds to a Simple Moving Average window of `span` elements.
+    ///
+    /// The smoothing factor is `alpha = 2 / (span + 1)`.
+    ///
+    /// # Panics
+    ///
+    /// Panics, if `span` is zero or if the resulting `alpha`
+    /// is too small to be represented by the accumulator type.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use movavg::Ema;
+    ///
+    /// let mut avg: Ema<f64, f64> = Ema::new_span(3); // alpha = 2 / (3 + 1)
+    /// assert_eq!(avg.feed(10.0), 10.0);
+    /// assert_eq!(avg.feed(20.0), 15.0);
+    /// ```
+    pub fn new_span(span: usize) -> Ema<T, A> {
+        assert!(span > 0);
+        Self::new(2, span.saturating_add(1))
+    }
+
+    /// Reset the Exponential Moving Average.
+    ///
+    /// The next fed value initializes the average again.
+    /// The smoothing factor is kept.
+    pub fn reset(&mut self) {
+        self.accu = A::zero();
+        self.populated = false;
+    }
+
+    /// Check if no items have been fed into the Exponential Moving Average, yet.
+    #[inline]
+    pub const fn is_empty(&self) -> bool {
+        !self.populated
+    }
+
+    /// Try to feed a new value into the Exponential Moving Average and return the new average.
+    ///
+    /// * `value` - The new value to feed into the Exponential Moving Average.
+    ///
+    /// On success, returns `Ok(T)` with the new Exponential Moving Average result.
+    ///
+    /// Returns `Err`, if the internal accumulator overflows, or if any value conversion fails.
+    /// Value conversion does not fail, if the types are big enough to hold the values.
+    pub fn try_feed(&mut self, value: T) -> Result<T, MovAvgError> {
+        let a_value = A::from(value).ok_or(MovAvgError::CastInput)?;
+
+        let new_accu = if self.populated {
+            self.accu.ema_update(self.alpha, a_value)?
+        } else {
+            A::ema_init(a_value)?
+        };
+
+        let avg = T::from(new_accu.ema_value()).ok_or(MovAvgError::CastResult)?;
+
+        self.accu = new_accu;
+        self.populated = true;
+
+        Ok(avg)
+    }
+
+    /// Feed a new value into the Exponential Moving Average and return the new average.
+    ///
+    /// * `value` - The new value to feed into the Exponential Moving Average.
+    ///
+    /// Returns the new Exponential Moving Average result.
+    ///
+    /// # Panics
+    ///
+    /// Panics, if the internal accumulator overflows, or if any value conversion fails.
+    /// Value conversion does not fail, if the types are big enough to hold the values.
+    pub fn feed(&mut self, value: T) -> T {
+        match self.try_feed(value) {
+            Ok(avg) => avg,
+            Err(e) => panic!("Ema calculation failed: {}", e),
+        }
+    }
+
+    /// Try to get the current Exponential Moving Average value.
+    /// This method does not modify the internal state.
+    ///
+    /// Returns `Err`, if the internal state is empty.
+    /// That is if no values have been fed into Ema.
+    ///
+    /// Returns `Err`, if any value conversion fails.
+    /// Value conversion does not fail, if the types are big enough to hold the values.
+    pub fn try_get(&self) -> Result<T, MovAvgError> {
+        if self.populated {
+            T::from(self.accu.ema_value()).ok_or(MovAvgError::CastResult)
+        } else {
+            Err(MovAvgError::Empty)
+        }
+    }
+
+    /// Get the current Exponential Moving Average value.
+    /// This method does not modify the internal state.
+    ///
+    /// # Panics
+    ///
+    /// Panics, if the internal state is empty.
+    /// That is if no values have been fed into Ema.
+    ///
+    /// Panics, if any value conversion fails.
+    /// Value conversion does not fail, if the types are big enough to hold the values.
+    pub fn get(&self) -> T {
+        match self.try_get() {
+            Ok(avg) => avg,
+            Err(MovAvgError::Empty) => panic!("Ema calculation failed: The Ema state is empty."),
+            Err(e) => panic!("Ema calculation failed: {}", e),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_alpha() {
+        assert!(<i32 as EmaAccu>::ema_alpha(0, 1).is_err());
+        assert!(<i32 as EmaAccu>::ema_alpha(2, 1).is_err());
+        assert_eq!(<i32 as EmaAccu>::ema_alpha(1, 1), Ok(256));
+        assert_eq!(<i32 as EmaAccu>::ema_alpha(1, 4), Ok(64));
+        assert_eq!(<i8 as EmaAccu>::ema_alpha(1, 4), Ok(1));
+        assert!(<i8 as EmaAccu>::ema_alpha(1, 5).is_err());
+        assert_eq!(<f64 as EmaAccu>::ema_alpha(1, 4), Ok(0.25));
+    }
+
+    #[test]
+    fn test_i32() {
+        let mut a: Ema<i32, i64> = Ema::new(1, 4);
+        assert!(a.is_empty());
+        assert_eq!(a.feed(100), 100);
+        assert!(!a.is_empty());
+        assert_eq!(a.feed(200), 125);
+        assert_eq!(a.feed(-100), 68); // 68.75
+        assert_eq!(a.feed(-100), 26); // 26.5625
+        assert_eq!(a.get(), 26);
+    }
+
+    #[test]
+    fn test_u16() {
+        let mut a: Ema<u16, u32> = Ema::new(1, 2);
+        assert_eq!(a.feed(1000), 1000);
+        assert_eq!(a.feed(0), 500);
+        assert_eq!(a.feed(0), 250);
+        assert_eq!(a.feed(1000), 625);
+        assert_eq!(a.feed(u16::MAX), 33080);
+    }
+
+    #[test]
+    fn test_converge() {
+        let mut a: Ema<u8, u32> = Ema::new_span(9);
+        assert_eq!(a.feed(0), 0);
+        for _ in 0..200 {
+            a.feed(250);
+        }
+        assert_eq!(a.get(), 249);
+        for _ in 0..200 {
+            a.feed(3);
+        }
+        assert_eq!(a.get(), 3);
+    }
+
+    #[test]
+    fn test_alpha_one() {
+        let mut a: Ema<i16, i16> = Ema::new(1, 1);
+        assert_eq!(a.feed(10), 10);
+        assert_eq!(a.feed(-20), -20);
+        assert_eq!(a.feed(30), 30);
+    }
+
+    #[test]
+    fn test_f64() {
+        let mut a: Ema<f64, f64> = Ema::new_span(4); // alpha = 0.4
+        let e = 0.000001;
+        assert!((a.feed(10.0) - 10.0).abs() < e);
+        assert!((a.feed(20.0) - 14.0).abs() < e);
+        assert!((a.feed(-5.0) - 6.4).abs() < e);
+        assert!((a.get() - 6.4).abs() < e);
+    }
+
+    #[test]
+    fn test_accu_overflow() {
+        let mut a: Ema<i16, i16> = Ema::new(1, 2);
+        assert_eq!(a.feed(100), 100);
+        assert!(a.try_feed(10_000).is_err());
+        assert_eq!(a.get(), 100);
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut a: Ema<i32, i32> = Ema::new(1, 2);
+        assert_eq!(a.feed(10), 10);
+        assert_eq!(a.feed(20), 15);
+        a.reset();
+        assert!(a.is_empty());
+        assert!(a.try_get().is_err());
+        assert_eq!(a.feed(40), 40);
+    }
+
+    #[test]
+    #[should_panic(expected = "The Ema state is empty")]
+    fn test_get_empty_panic() {
+        let a: Ema<i32, i32> = Ema::new(1, 2);
+        assert_eq!(a.get(), 42); // this panics
+    }
+
+    #[test]
+    #[should_panic(expected = "Invalid smoothing factor")]
+    fn test_invalid_alpha_panic() {
+        let _a: Ema<i32, i32> = Ema::new(3, 2); // this panics
+    }
+}
+
+// vim: ts=4 sw=4 expandtab
@@ -0,0 +1,112 @@
+// -*- coding: utf-8 -*-
+//
+// Copyright 2026 Michael Büsch <m@bues.ch>
+//
+// Licensed under the Apache License version 2.0
+// or the MIT license, at your option.
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//
+
+use core::fmt;
+
+/// Moving Average calculation error.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum MovAvgError {
+    /// The internal accumulator overflowed.
+    ///
+    /// Use a bigger accumulator type `A` to avoid this.
+    AccuOverflow,
+
+    /// Failed to cast an input value to the accumulator type.
+    CastInput,
+
+    /// Failed to cast the result to the item type.
+    CastResult,
+
+    /// Failed to cast the number of items to the accumulator type.
+    CastCount,
+
+    /// The Moving Average state is empty.
+    /// No values have been fed, yet.
+    Empty,
+
+    /// There are not enough items in the window for the calculation.
+    /// (e.g. the sample variance needs at least two items).
+    TooFewItems,
+
+    /// The window size is zero.
+    InvalidWindowSize,
+
+    /// The number of pre-populated items is bigger than the window size.
+    InvalidNrPopulated,
+
+    /// The smoothing factor is out of range
+    /// or it can't be represented by the accumulator type.
+    InvalidAlpha,
+
+    /// The quantile is not in the range `0.0..=1.0`.
+    InvalidQuantile,
+
+    /// The restored Moving Average state is inconsistent.
+    /// (e.g. the accumulator does not match the window buffer).
+    InvalidState,
+
+    /// The output buffer is too small.
+    BufferTooSmall,
+
+    /// The snapshot is truncated, has an unsupported layout version,
+    /// or does not match the Moving Average types.
+    InvalidSnapshot,
+
+    /// The snapshot checksum does not match.
+    SnapshotChecksum,
+}
+
+impl fmt::Display for MovAvgError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let msg = match self {
+            MovAvgError::AccuOverflow => "Accumulator type add overflow.",
+            MovAvgError::CastInput => "Failed to cast value to accumulator type.",
+            MovAvgError::CastResult => "Failed to cast result to item type.",
+            MovAvgError::CastCount => "Failed to cast number-of-items to accumulator type.",
+            MovAvgError::Empty => "The MovAvg state is empty.",
+            MovAvgError::TooFewItems => "Not enough items in the window.",
+            MovAvgError::InvalidWindowSize => "The window size is zero.",
+            MovAvgError::InvalidNrPopulated => {
+                "The number of pre-populated items is bigger than the window size."
+            }
+            MovAvgError::InvalidAlpha => "Invalid smoothing factor.",
+            MovAvgError::InvalidQuantile => "The quantile is out of range.",
+            MovAvgError::InvalidState => "The MovAvg state is inconsistent.",
+            MovAvgError::BufferTooSmall => "The output buffer is too small.",
+            MovAvgError::InvalidSnapshot => "The snapshot is invalid or incompatible.",
+            MovAvgError::SnapshotChecksum => "The snapshot checksum does not match.",
+        };
+        f.write_str(msg)
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for MovAvgError {}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_display() {
+        use std::string::ToString;
+
+        assert_eq!(
+            MovAvgError::AccuOverflow.to_string(),
+            "Accumulator type add overflow."
+        );
+        assert_eq!(MovAvgError::Empty.to_string(), "The MovAvg state is empty.");
+
+        let e: std::boxed::Box<dyn std::error::Error> = MovAvgError::CastInput.into();
+        assert_eq!(e.to_string(), "Failed to cast value to accumulator type.");
+    }
+}
+
+// vim: ts=4 sw=4 expandtab
@@ -0,0 +1,319 @@
+// -*- coding: utf-8 -*-
+//
+// Copyright 2026 Michael Büsch <m@bues.ch>
+//
+// Licensed under the Apache License version 2.0
+// or the MIT license, at your option.
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//
+
+use crate::{
+    error::MovAvgError,
+    sma::{MovAvg, MovAvgAccu},
+};
+use num_traits::{Num, NumCast};
+
+/// Extension trait for iterators to calculate the Simple Moving Average of the items.
+///
+/// This trait is implemented for all iterators.
+///
+/// # Examples
+///
+/// ```
+/// use movavg::MovAvgIterExt;
+///
+/// let samples = [10, 20, 30, 40, 50];
+///
+/// let avgs: Vec<i32> = samples.iter().copied().moving_avg::<i32, 3>().collect();
+/// assert_eq!(avgs, [10, 15, 20, 30, 40]);
+///
+/// // Skip the warm-up period until the window is fully populated.
+/// let avgs: Vec<i32> = samples
+///     .iter()
+///     .copied()
+///     .moving_avg::<i32, 3>()
+///     .skip_warmup()
+///     .collect();
+/// assert_eq!(avgs, [20, 30, 40]);
+///
+/// // Yield a `Result` instead of panicking.
+/// let avgs: Vec<_> = [100_u8, 200, 1].iter().copied().try_moving_avg::<u8, 2>().collect();
+/// assert_eq!(avgs[0], Ok(100));
+/// assert!(avgs[1].is_err()); // Accumulator overflow
+/// assert_eq!(avgs[2], Ok(50));
+/// ```
+pub trait MovAvgIterExt: Iterator + Sized {
+    /// Map each item through [MovAvg::feed] of a new [MovAvg] instance.
+    ///
+    /// The returned iterator panics, if [MovAvg::feed] panics.
+    fn moving_avg<A, const WINDOW_SIZE: usize>(self) -> MovAvgIter<Self, A, WINDOW_SIZE>
+    where
+        Self::Item: Num + NumCast + Copy,
+        A: Num + NumCast + Copy + MovAvgAccu<Self::Item>,
+    {
+        self.moving_avg_with(MovAvg::new())
+    }
+
+    /// Map each item through [MovAvg::feed] of the given [MovAvg] instance.
+    ///
+    /// The returned iterator panics, if [MovAvg::feed] panics.
+    fn moving_avg_with<A, const WINDOW_SIZE: usize>(
+        self,
+        avg: MovAvg<Self::Item, A, WINDOW_SIZE>,
+    ) -> MovAvgIter<Self, A, WINDOW_SIZE>
+    where
+        Self::Item: Num + NumCast + Copy,
+        A: Num + NumCast + Copy + MovAvgAccu<Self::Item>,
+    {
+        MovAvgIter {
+            iter: self,
+            avg,
+            skip_warmup: false,
+        }
+    }
+
+    /// Map each item through [MovAvg::try_feed] of a new [MovAvg] instance.
+    fn try_moving_avg<A, const WINDOW_SIZE: usize>(self) -> TryMovAvgIter<Self, A, WINDOW_SIZE>
+    where
+        Self::Item: Num + NumCast + Copy,
+        A: Num + NumCast + Copy + MovAvgAccu<Self::Item>,
+    {
+        self.try_moving_avg_with(MovAvg::new())
+    }
+
+    /// Map each item through [MovAvg::try_feed] of the given [MovAvg] instance.
+    fn try_moving_avg_with<A, const WINDOW_SIZE: usize>(
+        self,
+        avg: MovAvg<Self::Item, A, WINDOW_SIZE>,
+    ) -> TryMovAvgIter<Self, A, WINDOW_SIZE>
+    where
+        Self::Item: Num + NumCast + Copy,
+        A: Num + NumCast + Copy + MovAvgAccu<Self::Item>,
+    {
+        TryMovAvgIter {
+            iter: self,
+            avg,
+            skip_warmup: false,
+        }
+    }
+}
+
+impl<I: Iterator> MovAvgIterExt for I {}
+
+/// Number of items that are swallowed until the `avg` window is fully populated.
+fn warmup_left<T, A, const WINDOW_SIZE: usize>(avg: &MovAvg<T, A, WINDOW_SIZE>) -> usize
+where
+    T: Num + NumCast + Copy,
+    A: Num + NumCast + Copy + MovAvgAccu<T>,
+{
+    (WINDOW_SIZE - avg.len()).saturating_sub(1)
+}
+
+/// Reduce the size hint of the underlying iterator by the warm-up items.
+fn size_hint_skip(hint: (usize, Option<usize>), skip: usize) -> (usize, Option<usize>) {
+    let (lower, upper) = hint;
+    (
+        lower.saturating_sub(skip),
+        upper.map(|upper| upper.saturating_sub(skip)),
+    )
+}
+
+/// Iterator that yields the Simple Moving Average of the items of the underlying iterator.
+///
+/// See [MovAvgIterExt::moving_avg].
+#[derive(Clone, Debug)]
+pub struct MovAvgIter<I: Iterator, A, const WINDOW_SIZE: usize> {
+    iter: I,
+    avg: MovAvg<I::Item, A, WINDOW_SIZE>,
+    skip_warmup: bool,
+}
+
+impl<I, A, const WINDOW_SIZE: usize> MovAvgIter<I, A, WINDOW_SIZE>
+where
+    I: Iterator,
+    I::Item: Num + NumCast + Copy,
+    A: Num + NumCast + Copy + MovAvgAccu<I::Item>,
+{
+    /// Only yield averages after the window has been fully populated.
+    ///
+    /// That is if [MovAvg::len] `==` [MovAvg::window_size].
+    pub fn skip_warmup(mut self) -> Self {
+        self.skip_warmup = true;
+        self
+    }
+
+    /// Get the Moving Average state.
+    pub fn movavg(&self) -> &MovAvg<I::Item, A, WINDOW_SIZE> {
+        &self.avg
+    }
+
+    /// Destroy the iterator and return the Moving Average state.
+    pub fn into_movavg(self) -> MovAvg<I::Item, A, WINDOW_SIZE> {
+        self.avg
+    }
+}
+
+impl<I, A, const WINDOW_SIZE: usize> Iterator for MovAvgIter<I, A, WINDOW_SIZE>
+where
+    I: Iterator,
+    I::Item: Num + NumCast + Copy,
+    A: Num + NumCast + Copy + MovAvgAccu<I::Item>,
+{
+    type Item = I::Item;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let avg = self.avg.feed(self.iter.next()?);
+            if !self.skip_warmup || self.avg.len() >= WINDOW_SIZE {
+                return Some(avg);
+            }
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        if self.skip_warmup {
+            size_hint_skip(self.iter.size_hint(), warmup_left(&self.avg))
+        } else {
+            self.iter.size_hint()
+        }
+    }
+}
+
+/// Iterator that yields the Simple Moving Average results of the items of the underlying iterator.
+///
+/// See [MovAvgIterExt::try_moving_avg].
+#[derive(Clone, Debug)]
+pub struct TryMovAvgIter<I: Iterator, A, const WINDOW_SIZE: usize> {
+    iter: I,
+    avg: MovAvg<I::Item, A, WINDOW_SIZE>,
+    skip_warmup: bool,
+}
+
+impl<I, A, const WINDOW_SIZE: usize> TryMovAvgIter<I, A, WINDOW_SIZE>
+where
+    I: Iterator,
+    I::Item: Num + NumCast + Copy,
+    A: Num + NumCast + Copy + MovAvgAccu<I::Item>,
+{
+    /// Only yield successful averages after the window has been fully populated.
+    ///
+    /// That is if [MovAvg::len] `==` [MovAvg::window_size].
+    /// Errors are always yielded.
+    pub fn skip_warmup(mut self) -> Self {
+        self.skip_warmup = true;
+        self
+    }
+
+    /// Get the Moving Average state.
+    pub fn movavg(&self) -> &MovAvg<I::Item, A, WINDOW_SIZE> {
+        &self.avg
+    }
+
+    /// Destroy the iterator and return the Moving Average state.
+    pub fn into_movavg(self) -> MovAvg<I::Item, A, WINDOW_SIZE> {
+        self.avg
+    }
+}
+
+impl<I, A, const WINDOW_SIZE: usize> Iterator for TryMovAvgIter<I, A, WINDOW_SIZE>
+where
+    I: Iterator,
+    I::Item: Num + NumCast + Copy,
+    A: Num + NumCast + Copy + MovAvgAccu<I::Item>,
+{
+    type Item = Result<I::Item, MovAvgError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let res = self.avg.try_feed(self.iter.next()?);
+            if res.is_err() || !self.skip_warmup || self.avg.len() >= WINDOW_SIZE {
+                return Some(res);
+            }
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        if self.skip_warmup {
+            // Errors are yielded during warm-up, too.
+            let (_, upper) = self.iter.size_hint();
+            (0, upper)
+        } else {
+            self.iter.size_hint()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::AccuMode;
+
+    #[test]
+    fn test_moving_avg() {
+        let samples = [1.0, 2.0, 3.0, 4.0];
+        let mut it = samples.iter().copied().moving_avg::<f64, 2>();
+        assert_eq!(it.size_hint(), (4, Some(4)));
+        assert_eq!(it.next(), Some(1.0));
+        assert_eq!(it.next(), Some(1.5));
+        assert_eq!(it.next(), Some(2.5));
+        assert_eq!(it.movavg().len(), 2);
+        assert_eq!(it.next(), Some(3.5));
+        assert_eq!(it.next(), None);
+        assert_eq!(it.into_movavg().get(), 3.5);
+    }
+
+    #[test]
+    fn test_skip_warmup() {
+        let samples = [1_u8, 2, 3, 4, 5];
+        let mut it = samples.iter().copied().moving_avg::<u16, 3>().skip_warmup();
+        assert_eq!(it.size_hint(), (3, Some(3)));
+        assert_eq!(it.next(), Some(2));
+        assert_eq!(it.size_hint(), (2, Some(2)));
+        assert_eq!(it.next(), Some(3));
+        assert_eq!(it.next(), Some(4));
+        assert_eq!(it.next(), None);
+
+        // The window is never fully populated.
+        let mut it = samples.iter().copied().moving_avg::<u16, 6>().skip_warmup();
+        assert_eq!(it.size_hint(), (0, Some(0)));
+        assert_eq!(it.next(), None);
+
+        // Pre-populated window.
+        let avg: MovAvg<u8, u16, 3> = MovAvg::new_init([10, 20, 30], 2);
+        let mut it = samples.iter().copied().moving_avg_with(avg).skip_warmup();
+        assert_eq!(it.next(), Some(10)); // 31 / 3
+    }
+
+    #[test]
+    fn test_try_moving_avg() {
+        let samples = [200_u8, 100, 10, 20, 250];
+        let it = samples.iter().copied().try_moving_avg::<u8, 2>();
+        assert!(it.eq([
+            Ok(200),
+            Err(MovAvgError::AccuOverflow),
+            Ok(105),
+            Ok(15),
+            Err(MovAvgError::AccuOverflow),
+        ]));
+
+        let it = samples
+            .iter()
+            .copied()
+            .try_moving_avg::<u8, 2>()
+            .skip_warmup();
+        assert!(it.eq([
+            Err(MovAvgError::AccuOverflow),
+            Ok(105),
+            Ok(15),
+            Err(MovAvgError::AccuOverflow),
+        ]));
+
+        let avg: MovAvg<f32, f32, 2> = MovAvg::new().with_accu_mode(AccuMode::Compensated);
+        let mut it = [1.0, 3.0].iter().copied().try_moving_avg_with(avg);
+        assert_eq!(it.next(), Some(Ok(1.0)));
+        assert_eq!(it.next(), Some(Ok(2.0)));
+        assert_eq!(it.movavg().accu_mode(), AccuMode::Compensated);
+    }
+}
+
+// vim: ts=4 sw=4 expandtab
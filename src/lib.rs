@@ -9,7 +9,11 @@
 
 //! # Moving Average
 //!
-//! Generic Moving Average calculation for the integer types
+//! Generic Simple Moving Average ([MovAvg]), Exponential Moving Average ([Ema])
+//! and Linearly Weighted Moving Average ([Wma]) calculation,
+//! as well as moving variance and standard deviation ([MovVar]),
+//! moving minimum and maximum ([MovMinMax]), moving median ([MovMedian])
+//! and moving quantiles ([MovQuantile]), for the integer types
 //!
 //! * i8, i16, i32, i64, i128, isize
 //! * u8, u16, u32, u64, u128, usize
@@ -18,19 +22,75 @@
 //!
 //! * f32, f64
 //!
+//! The [MovAvgIterExt] trait adapts any iterator into an iterator of averages.
+//!
+//! The state of a [MovAvg] can be stored in a compact binary snapshot
+//! with [MovAvg::to_bytes] and restored with [MovAvg::from_bytes].
+//!
 //! # Cargo Features
 //!
 //! * `std` - If the cargo feature `std` is given, then all features that depend on
-//!           the `std` library are enabled. This feature is enabled by default.
-//!           Use `default-features = false` in your `Cargo.toml` to disable this feature.
-//!           This crate is independent of the `std` library, if this feature is disabled.
+//!   the `std` library are enabled. This feature is enabled by default.
+//!   Use `default-features = false` in your `Cargo.toml` to disable this feature.
+//!   This crate is independent of the `std` library, if this feature is disabled.
+//!   The `std` feature implies the `alloc` feature.
+//! * `libm` - If the cargo feature `libm` is given, then the float math functions
+//!   of the `libm` library are used, if the `std` feature is disabled.
+//!   This is required for [MovVar] with float accumulators without the `std` library.
+//!   This feature is disabled by default.
+//! * `alloc` - If the cargo feature `alloc` is given, then all features that depend on
+//!   the `alloc` library are enabled. This includes [DynMovAvg].
+//!   This feature is enabled by default via the `std` feature.
+//! * `futures` - If the cargo feature `futures` is given, then the `MovAvgStreamExt`
+//!   adapter for async streams of the `futures` ecosystem is enabled.
+//!   It does not depend on any particular async runtime.
+//!   This feature is disabled by default.
+//! * `serde` - If the cargo feature `serde` is given, then [MovAvg] implements
+//!   `serde::Serialize` and `serde::Deserialize`.
+//!   The window buffer and the complete calculation state are serialized.
+//!   The state is validated on deserialization.
+//!   This feature is disabled by default.
 
 #![no_std]
+#[cfg(feature = "alloc")]
+extern crate alloc;
 #[cfg(feature = "std")]
 extern crate std;
 
+mod ema;
+mod error;
+mod iter;
+mod median;
+mod minmax;
+mod quantile;
 mod sma;
+#[cfg(feature = "alloc")]
+mod sma_dyn;
+mod sma_ref;
+#[cfg(feature = "serde")]
+mod sma_serde;
+mod snapshot;
+#[cfg(feature = "futures")]
+mod stream;
+mod var;
+mod wide;
+mod wma;
 
-pub use sma::{MovAvg, MovAvgAccu};
+pub use ema::{Ema, EmaAccu};
+pub use error::MovAvgError;
+pub use iter::{MovAvgIter, MovAvgIterExt, TryMovAvgIter};
+pub use median::{MedianPolicy, MovMedian};
+pub use minmax::MovMinMax;
+pub use quantile::{MovQuantile, QuantileMethod};
+pub use sma::{AccuMode, MovAvg, MovAvgAccu, OverflowPolicy, Rounding, WindowIter};
+#[cfg(feature = "alloc")]
+pub use sma_dyn::DynMovAvg;
+pub use sma_ref::MovAvgRef;
+pub use snapshot::SnapshotValue;
+#[cfg(feature = "futures")]
+pub use stream::{MovAvgStream, MovAvgStreamExt};
+pub use var::{MovVar, MovVarAccu};
+pub use wide::{WideAccu, WideMovAvg};
+pub use wma::{Wma, WmaAccu};
 
 // vim: ts=4 sw=4 expandtab
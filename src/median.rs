@@ -0,0 +1,435 @@
+// -*- coding: utf-8 -*-
+//
+// Copyright 2026 Michael Büsch <m@bues.ch>
+//
+// Licensed under the Apache License version 2.0
+// or the MIT license, at your option.
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//
+
+use crate::error::MovAvgError;
+use num_traits::Num;
+
+/// Median selection policy for an even number of items in the window.
+///
+/// If the number of items is odd, then there is exactly one middle item
+/// and the policy has no effect.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum MedianPolicy {
+    /// Use the lower one of the two middle items.
+    Lower,
+    /// Use the upper one of the two middle items.
+    Upper,
+    /// Use the mean of the two middle items.
+    ///
+    /// For integers the mean is truncated towards zero.
+    Mean,
+}
+
+impl Default for MedianPolicy {
+    /// The default policy is [MedianPolicy::Mean].
+    fn default() -> Self {
+        MedianPolicy::Mean
+    }
+}
+
+/// Calculate the mean of two values without intermediate overflow.
+///
+/// For integers the result is truncated towards zero.
+fn mean_of_two<T: Num + PartialOrd + Copy>(a: T, b: T) -> T {
+    let two = T::one() + T::one();
+    let (a_half, b_half) = (a / two, b / two);
+    // The remainders are zero for floats.
+    let rem = (a - (a_half * two)) + (b - (b_half * two));
+    let mean = a_half + b_half + (rem / two);
+    // Round a remaining half towards zero.
+    let rem = rem - ((rem / two) * two);
+    if rem > T::zero() && mean < T::zero() {
+        mean + T::one()
+    } else if rem < T::zero() && mean > T::zero() {
+        mean - T::one()
+    } else {
+        mean
+    }
+}
+
+/// Ring buffer window with a sorted view of the window items.
+///
+/// This is the common core of [MovMedian] and [crate::MovQuantile].
+#[derive(Clone, Debug)]
+pub(crate) struct SortedWindow<T, const WINDOW_SIZE: usize> {
+    buffer: [T; WINDOW_SIZE],
+    sorted: [usize; WINDOW_SIZE],
+    nr_items: usize,
+    index: usize,
+}
+
+impl<T, const WINDOW_SIZE: usize> SortedWindow<T, WINDOW_SIZE>
+where
+    T: Num + PartialOrd + Copy,
+{
+    pub fn new() -> Self {
+        SortedWindow {
+            buffer: [T::zero(); WINDOW_SIZE],
+            sorted: [0; WINDOW_SIZE],
+            nr_items: 0,
+            index: 0,
+        }
+    }
+
+    pub fn reset(&mut self) {
+        self.nr_items = 0;
+        self.index = 0;
+    }
+
+    #[inline]
+    pub const fn len(&self) -> usize {
+        self.nr_items
+    }
+
+    /// Feed a new value into the window and evict the oldest one, if the window is full.
+    pub fn feed(&mut self, value: T) {
+        let mut nr_sorted = self.nr_items;
+
+        if nr_sorted >= WINDOW_SIZE {
+            // The oldest item is at self.index. Remove it from the sorted view.
+            if let Some(pos) = self.sorted.iter().position(|&i| i == self.index) {
+                self.sorted.copy_within(pos + 1.., pos);
+            }
+            nr_sorted -= 1;
+        }
+
+        self.buffer[self.index] = value;
+
+        // Insert the new item into the sorted view.
+        let buffer = &self.buffer;
+        let pos = self.sorted[..nr_sorted].partition_point(|&i| buffer[i] < value);
+        self.sorted.copy_within(pos..nr_sorted, pos + 1);
+        self.sorted[pos] = self.index;
+
+        self.nr_items = nr_sorted + 1;
+        self.index = (self.index + 1) % WINDOW_SIZE;
+    }
+
+    /// Get the item with the given `rank` in the sorted window.
+    ///
+    /// `rank` must be less than [Self::len].
+    #[inline]
+    pub fn sorted_item(&self, rank: usize) -> T {
+        debug_assert!(rank < self.nr_items);
+        self.buffer[self.sorted[rank]]
+    }
+}
+
+/// # Moving median
+///
+/// The median is the middle value of the sorted window.
+/// In contrast to [crate::MovAvg] a single outlier value
+/// (e.g. an impulse noise spike) does not affect the result.
+///
+/// A sorted view of the window is maintained alongside the ring buffer.
+/// Feeding a new value takes `O(WINDOW_SIZE)` time.
+/// All storage has a fixed size and no heap allocation is done.
+///
+/// ```
+/// use movavg::{MedianPolicy, MovMedian};
+///
+/// let mut med: MovMedian<i32, 3> = MovMedian::new(); // window size = 3
+/// assert_eq!(med.feed(10), 10);
+/// assert_eq!(med.feed(12), 11); // mean of 10 and 12
+/// assert_eq!(med.feed(1000), 12); // spike is rejected
+/// assert_eq!(med.feed(11), 12);
+/// assert_eq!(med.feed(13), 13);
+///
+/// let mut med: MovMedian<i32, 4> = MovMedian::new().with_policy(MedianPolicy::Lower);
+/// med.feed(1);
+/// med.feed(2);
+/// med.feed(3);
+/// assert_eq!(med.feed(4), 2);
+/// ```
+///
+/// # Type Generics
+///
+/// `struct MovMedian<T, WINDOW_SIZE>`
+///
+/// * `T` - The type of the `feed()` input value.
+/// * `WINDOW_SIZE` - The size of the sliding window.
+///   In number of fed elements.
+///
+/// # Float values
+///
+/// `NaN` values are not ordered with respect to other values.
+/// Feeding `NaN` leads to unspecified (but memory safe) results
+/// until the `NaN` value has been evicted from the window.
+#[derive(Clone, Debug)]
+pub struct MovMedian<T, const WINDOW_SIZE: usize> {
+    window: SortedWindow<T, WINDOW_SIZE>,
+    policy: MedianPolicy,
+}
+
+impl<T, const WINDOW_SIZE: usize> MovMedian<T, WINDOW_SIZE>
+where
+    T: Num + PartialOrd + Copy,
+{
+    /// Compile time check of the `WINDOW_SIZE` generic.
+    const WINDOW_SIZE_CHECK: () = assert!(WINDOW_SIZE > 0, "WINDOW_SIZE must not be zero.");
+
+    /// Construct a new moving median.
+    ///
+    /// The even window policy defaults to [MedianPolicy::Mean].
+    pub fn new() -> MovMedian<T, WINDOW_SIZE> {
+        let () = Self::WINDOW_SIZE_CHECK;
+        MovMedian {
+            window: SortedWindow::new(),
+            policy: MedianPolicy::default(),
+        }
+    }
+
+    /// Set the median selection policy for an even number of items in the window.
+    pub fn with_policy(mut self, policy: MedianPolicy) -> Self {
+        self.policy = policy;
+        self
+    }
+
+    /// Get the median selection policy for an even number of items in the window.
+    #[inline]
+    pub const fn policy(&self) -> MedianPolicy {
+        self.policy
+    }
+
+    /// Reset the moving median.
+    ///
+    /// This resets the number of accumulated items to 0,
+    /// as if this instance was re-created with [MovMedian::new].
+    /// The median selection policy is kept.
+    pub fn reset(&mut self) {
+        self.window.reset();
+    }
+
+    /// Get the current length of the window.
+    ///
+    /// This length is in the range of `0..WINDOW_SIZE`.
+    /// If the length is less than `WINDOW_SIZE`, then the window
+    /// has not been fully populated, yet.
+    ///
+    /// See [Self::window_size] to get the nominal maximum length of the window.
+    #[inline]
+    pub const fn len(&self) -> usize {
+        self.window.len()
+    }
+
+    /// Check if no items have been fed, yet.
+    ///
+    /// This returns true, if [Self::len] `==0`.
+    ///
+    /// This returns false, if [Self::len] `!=0`.
+    #[inline]
+    pub const fn is_empty(&self) -> bool {
+        self.window.len() == 0
+    }
+
+    /// Get the nominal size of the window.
+    ///
+    /// This is always equal to `WINDOW_SIZE`.
+    ///
+    /// See [Self::len] to get the actual current length of the window.
+    #[inline]
+    pub const fn window_size(&self) -> usize {
+        WINDOW_SIZE
+    }
+
+    /// Try to feed a new value into the window and return the new median.
+    ///
+    /// If the window is fully populated, then the oldest value is evicted.
+    ///
+    /// * `value` - The new value to feed into the window.
+    ///
+    /// Feeding a value into the moving median can't fail.
+    /// This method is provided for symmetry with the other Moving Average types.
+    pub fn try_feed(&mut self, value: T) -> Result<T, MovAvgError> {
+        self.window.feed(value);
+        Ok(self.median())
+    }
+
+    /// Feed a new value into the window and return the new median.
+    ///
+    /// If the window is fully populated, then the oldest value is evicted.
+    ///
+    /// * `value` - The new value to feed into the window.
+    pub fn feed(&mut self, value: T) -> T {
+        self.window.feed(value);
+        self.median()
+    }
+
+    /// Try to get the current median of the window.
+    /// This method does not modify the internal state.
+    ///
+    /// Returns `Err`, if the internal state is empty.
+    /// That is if no values have been fed into MovMedian.
+    pub fn try_get(&self) -> Result<T, MovAvgError> {
+        if self.is_empty() {
+            Err(MovAvgError::Empty)
+        } else {
+            Ok(self.median())
+        }
+    }
+
+    /// Get the current median of the window.
+    /// This method does not modify the internal state.
+    ///
+    /// # Panics
+    ///
+    /// Panics, if the internal state is empty.
+    /// That is if no values have been fed into MovMedian.
+    pub fn get(&self) -> T {
+        match self.try_get() {
+            Ok(median) => median,
+            Err(MovAvgError::Empty) => {
+                panic!("MovMedian calculation failed: The MovMedian state is empty.")
+            }
+            Err(e) => panic!("MovMedian calculation failed: {}", e),
+        }
+    }
+
+    /// Calculate the median of the non-empty window.
+    fn median(&self) -> T {
+        let n = self.window.len();
+        let upper = self.window.sorted_item(n / 2);
+        if n % 2 != 0 {
+            return upper;
+        }
+        let lower = self.window.sorted_item((n / 2) - 1);
+        match self.policy {
+            MedianPolicy::Lower => lower,
+            MedianPolicy::Upper => upper,
+            MedianPolicy::Mean => mean_of_two(lower, upper),
+        }
+    }
+}
+
+impl<T, const WINDOW_SIZE: usize> Default for MovMedian<T, WINDOW_SIZE>
+where
+    T: Num + PartialOrd + Copy,
+{
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Pseudo random number generator (xorshift).
+    fn rand(state: &mut u32) -> u32 {
+        *state ^= *state << 13;
+        *state ^= *state >> 17;
+        *state ^= *state << 5;
+        *state
+    }
+
+    /// Reference implementation of the lower and upper median.
+    fn median(window: &[i32]) -> (i32, i32) {
+        let mut sorted = [0; 64];
+        let sorted = &mut sorted[..window.len()];
+        sorted.copy_from_slice(window);
+        sorted.sort_unstable();
+        let n = sorted.len();
+        if n % 2 == 0 {
+            (sorted[(n / 2) - 1], sorted[n / 2])
+        } else {
+            (sorted[n / 2], sorted[n / 2])
+        }
+    }
+
+    fn check_random<const WINDOW_SIZE: usize>() {
+        let mut lower: MovMedian<i32, WINDOW_SIZE> =
+            MovMedian::new().with_policy(MedianPolicy::Lower);
+        let mut upper: MovMedian<i32, WINDOW_SIZE> =
+            MovMedian::new().with_policy(MedianPolicy::Upper);
+        let mut hist = [0_i32; 1000];
+        let mut state = 0x8765_4321;
+        for i in 0..hist.len() {
+            hist[i] = (rand(&mut state) % 20) as i32 - 10;
+            let window = &hist[(i + 1).saturating_sub(WINDOW_SIZE)..=i];
+            let (l, u) = median(window);
+            assert_eq!(lower.feed(hist[i]), l);
+            assert_eq!(upper.feed(hist[i]), u);
+            assert_eq!(lower.len(), window.len());
+        }
+    }
+
+    #[test]
+    fn test_random() {
+        check_random::<1>();
+        check_random::<2>();
+        check_random::<3>();
+        check_random::<10>();
+        check_random::<64>();
+    }
+
+    #[test]
+    fn test_mean_of_two() {
+        assert_eq!(mean_of_two(3_i32, 4), 3);
+        assert_eq!(mean_of_two(-1_i32, 0), 0);
+        assert_eq!(mean_of_two(-3_i32, -4), -3);
+        assert_eq!(mean_of_two(i8::MIN, i8::MAX), 0);
+        assert_eq!(mean_of_two(i8::MAX, i8::MAX), i8::MAX);
+        assert_eq!(mean_of_two(u8::MAX, u8::MAX - 1), u8::MAX - 1);
+        assert_eq!(mean_of_two(-3_i32, 4), 0);
+        assert_eq!(mean_of_two(3_i32, -4), 0);
+        assert_eq!(mean_of_two(-5_i32, -3), -4);
+        assert_eq!(mean_of_two(3.0_f64, 4.0), 3.5);
+        assert_eq!(mean_of_two(-1.0_f32, 0.0), -0.5);
+    }
+
+    #[test]
+    fn test_policy() {
+        let mut med: MovMedian<u8, 4> = MovMedian::default();
+        assert_eq!(med.policy(), MedianPolicy::Mean);
+        assert_eq!(med.try_get(), Err(MovAvgError::Empty));
+        assert_eq!(med.feed(10), 10);
+        assert_eq!(med.feed(21), 15);
+        assert_eq!(med.get(), 15);
+        let mut med = med.with_policy(MedianPolicy::Upper);
+        assert_eq!(med.get(), 21);
+        assert_eq!(med.feed(255), 21);
+        assert_eq!(med.feed(0), 21);
+        let med = med.with_policy(MedianPolicy::Lower);
+        assert_eq!(med.get(), 10);
+        assert_eq!(med.len(), 4);
+        assert_eq!(med.window_size(), 4);
+    }
+
+    #[test]
+    fn test_f64() {
+        let mut med: MovMedian<f64, 2> = MovMedian::new();
+        assert_eq!(med.feed(1.0), 1.0);
+        assert_eq!(med.feed(2.0), 1.5);
+        assert_eq!(med.feed(-2.0), 0.0);
+        assert_eq!(med.try_feed(4.0), Ok(1.0));
+        assert_eq!(med.try_get(), Ok(1.0));
+    }
+
+    #[test]
+    #[should_panic(expected = "The MovMedian state is empty")]
+    fn test_get_empty_panic() {
+        let med: MovMedian<i32, 3> = MovMedian::new();
+        assert_eq!(med.get(), 42); // this panics
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut med: MovMedian<i16, 3> = MovMedian::new().with_policy(MedianPolicy::Lower);
+        med.feed(1);
+        med.feed(2);
+        med.reset();
+        assert!(med.is_empty());
+        assert_eq!(med.try_get(), Err(MovAvgError::Empty));
+        assert_eq!(med.policy(), MedianPolicy::Lower);
+        assert_eq!(med.feed(7), 7);
+    }
+}
+
+// vim: ts=4 sw=4 expandtab
@@ -0,0 +1,380 @@
+// -*- coding: utf-8 -*-
+//
+// Copyright 2026 Michael Büsch <m@bues.ch>
+//
+// Licensed under the Apache License version 2.0
+// or the MIT license, at your option.
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//
+
+use num_traits::{Bounded, Num};
+
+/// Double ended queue of window buffer indices in fixed size storage.
+#[derive(Clone, Debug)]
+struct IndexDeque<const SIZE: usize> {
+    buf: [usize; SIZE],
+    head: usize,
+    len: usize,
+}
+
+impl<const SIZE: usize> IndexDeque<SIZE> {
+    const fn new() -> Self {
+        IndexDeque {
+            buf: [0; SIZE],
+            head: 0,
+            len: 0,
+        }
+    }
+
+    fn clear(&mut self) {
+        self.head = 0;
+        self.len = 0;
+    }
+
+    fn front(&self) -> Option<usize> {
+        if self.len == 0 {
+            None
+        } else {
+            Some(self.buf[self.head])
+        }
+    }
+
+    fn back(&self) -> Option<usize> {
+        if self.len == 0 {
+            None
+        } else {
+            Some(self.buf[(self.head + self.len - 1) % SIZE])
+        }
+    }
+
+    fn pop_front(&mut self) {
+        debug_assert!(self.len > 0);
+        self.head = (self.head + 1) % SIZE;
+        self.len -= 1;
+    }
+
+    fn pop_back(&mut self) {
+        debug_assert!(self.len > 0);
+        self.len -= 1;
+    }
+
+    fn push_back(&mut self, index: usize) {
+        debug_assert!(self.len < SIZE);
+        self.buf[(self.head + self.len) % SIZE] = index;
+        self.len += 1;
+    }
+}
+
+/// # Moving minimum and maximum
+///
+/// The minimum and maximum of the window are tracked with two monotonic queues.
+/// Feeding a value and querying the minimum or maximum
+/// is done in amortized `O(1)` time.
+/// All storage has a fixed size and no heap allocation is done.
+///
+/// ```
+/// use movavg::MovMinMax;
+///
+/// let mut mm: MovMinMax<i32, 3> = MovMinMax::new(); // window size = 3
+/// assert_eq!(mm.min(), None);
+/// mm.feed(10);
+/// mm.feed(-5);
+/// mm.feed(20);
+/// assert_eq!(mm.min(), Some(-5));
+/// assert_eq!(mm.max(), Some(20));
+/// assert_eq!(mm.range(), Some(25));
+/// mm.feed(7); // 10 is evicted
+/// mm.feed(8); // -5 is evicted
+/// assert_eq!(mm.min(), Some(7));
+/// assert_eq!(mm.max(), Some(20));
+/// assert_eq!(mm.range(), Some(13));
+/// ```
+///
+/// # Type Generics
+///
+/// `struct MovMinMax<T, WINDOW_SIZE>`
+///
+/// * `T` - The type of the `feed()` input value.
+/// * `WINDOW_SIZE` - The size of the sliding window.
+///   In number of fed elements.
+///
+/// # Float values
+///
+/// `NaN` values are not ordered with respect to other values.
+/// Feeding `NaN` leads to unspecified (but memory safe) results
+/// until the `NaN` value has been evicted from the window.
+#[derive(Clone, Debug)]
+pub struct MovMinMax<T, const WINDOW_SIZE: usize> {
+    buffer: [T; WINDOW_SIZE],
+    min_queue: IndexDeque<WINDOW_SIZE>,
+    max_queue: IndexDeque<WINDOW_SIZE>,
+    nr_items: usize,
+    index: usize,
+}
+
+impl<T, const WINDOW_SIZE: usize> MovMinMax<T, WINDOW_SIZE>
+where
+    T: Num + PartialOrd + Copy,
+{
+    /// Compile time check of the `WINDOW_SIZE` generic.
+    const WINDOW_SIZE_CHECK: () = assert!(WINDOW_SIZE > 0, "WINDOW_SIZE must not be zero.");
+
+    /// Construct a new moving minimum and maximum.
+    pub fn new() -> MovMinMax<T, WINDOW_SIZE> {
+        let () = Self::WINDOW_SIZE_CHECK;
+        MovMinMax {
+            buffer: [T::zero(); WINDOW_SIZE],
+            min_queue: IndexDeque::new(),
+            max_queue: IndexDeque::new(),
+            nr_items: 0,
+            index: 0,
+        }
+    }
+
+    /// Reset the moving minimum and maximum.
+    ///
+    /// This resets the number of accumulated items to 0,
+    /// as if this instance was re-created with [MovMinMax::new].
+    pub fn reset(&mut self) {
+        self.min_queue.clear();
+        self.max_queue.clear();
+        self.nr_items = 0;
+        self.index = 0;
+    }
+
+    /// Get the current length of the window.
+    ///
+    /// This length is in the range of `0..WINDOW_SIZE`.
+    /// If the length is less than `WINDOW_SIZE`, then the window
+    /// has not been fully populated, yet.
+    ///
+    /// See [Self::window_size] to get the nominal maximum length of the window.
+    #[inline]
+    pub const fn len(&self) -> usize {
+        self.nr_items
+    }
+
+    /// Check if no items have been fed, yet.
+    ///
+    /// This returns true, if [Self::len] `==0`.
+    ///
+    /// This returns false, if [Self::len] `!=0`.
+    #[inline]
+    pub const fn is_empty(&self) -> bool {
+        self.nr_items == 0
+    }
+
+    /// Get the nominal size of the window.
+    ///
+    /// This is always equal to `WINDOW_SIZE`.
+    ///
+    /// See [Self::len] to get the actual current length of the window.
+    #[inline]
+    pub const fn window_size(&self) -> usize {
+        WINDOW_SIZE
+    }
+
+    /// Feed a new value into the window.
+    ///
+    /// If the window is fully populated, then the oldest value is evicted.
+    ///
+    /// * `value` - The new value to feed into the window.
+    pub fn feed(&mut self, value: T) {
+        if self.nr_items >= WINDOW_SIZE {
+            // The oldest item is at self.index. Evict it from the queues.
+            if self.min_queue.front() == Some(self.index) {
+                self.min_queue.pop_front();
+            }
+            if self.max_queue.front() == Some(self.index) {
+                self.max_queue.pop_front();
+            }
+        } else {
+            self.nr_items += 1;
+        }
+
+        // Drop all items that can never become the minimum or maximum again.
+        while let Some(i) = self.min_queue.back() {
+            if self.buffer[i] >= value {
+                self.min_queue.pop_back();
+            } else {
+                break;
+            }
+        }
+        while let Some(i) = self.max_queue.back() {
+            if self.buffer[i] <= value {
+                self.max_queue.pop_back();
+            } else {
+                break;
+            }
+        }
+
+        self.buffer[self.index] = value;
+        self.min_queue.push_back(self.index);
+        self.max_queue.push_back(self.index);
+        self.index = (self.index + 1) % WINDOW_SIZE;
+    }
+
+    /// Get the minimum value of the window.
+    ///
+    /// Returns `None`, if no items have been fed, yet.
+    pub fn min(&self) -> Option<T> {
+        self.min_queue.front().map(|i| self.buffer[i])
+    }
+
+    /// Get the maximum value of the window.
+    ///
+    /// Returns `None`, if no items have been fed, yet.
+    pub fn max(&self) -> Option<T> {
+        self.max_queue.front().map(|i| self.buffer[i])
+    }
+
+    /// Get the range of the window.
+    ///
+    /// That is the maximum value minus the minimum value.
+    ///
+    /// Returns `None`, if no items have been fed, yet.
+    ///
+    /// Returns `None`, if the range does not fit into `T`.
+    /// (e.g. the `i8` items -100 and 100).
+    pub fn range(&self) -> Option<T>
+    where
+        T: Bounded,
+    {
+        match (self.min(), self.max()) {
+            (Some(min), Some(max)) => {
+                // max - min overflows, if max > T::MAX + min.
+                // T::MAX + min does not overflow for negative min.
+                if min < T::zero() && max > T::max_value() + min {
+                    None
+                } else {
+                    Some(max - min)
+                }
+            }
+            _ => None,
+        }
+    }
+}
+
+impl<T, const WINDOW_SIZE: usize> Default for MovMinMax<T, WINDOW_SIZE>
+where
+    T: Num + PartialOrd + Copy,
+{
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Pseudo random number generator (xorshift).
+    fn rand(state: &mut u32) -> u32 {
+        *state ^= *state << 13;
+        *state ^= *state >> 17;
+        *state ^= *state << 5;
+        *state
+    }
+
+    fn check_random<const WINDOW_SIZE: usize>() {
+        let mut mm: MovMinMax<i32, WINDOW_SIZE> = MovMinMax::new();
+        let mut hist = [0_i32; 1000];
+        let mut state = 0x1234_5678;
+        for i in 0..hist.len() {
+            hist[i] = (rand(&mut state) % 100) as i32 - 50;
+            mm.feed(hist[i]);
+            let window = &hist[(i + 1).saturating_sub(WINDOW_SIZE)..=i];
+            assert_eq!(mm.len(), window.len());
+            assert_eq!(mm.min(), window.iter().copied().min());
+            assert_eq!(mm.max(), window.iter().copied().max());
+        }
+    }
+
+    #[test]
+    fn test_random() {
+        check_random::<1>();
+        check_random::<2>();
+        check_random::<3>();
+        check_random::<10>();
+        check_random::<64>();
+    }
+
+    #[test]
+    fn test_i32() {
+        let mut mm: MovMinMax<i32, 3> = MovMinMax::new();
+        assert!(mm.is_empty());
+        assert_eq!(mm.min(), None);
+        assert_eq!(mm.max(), None);
+        assert_eq!(mm.range(), None);
+        mm.feed(5);
+        assert_eq!(mm.len(), 1);
+        assert_eq!(mm.min(), Some(5));
+        assert_eq!(mm.max(), Some(5));
+        assert_eq!(mm.range(), Some(0));
+        mm.feed(5);
+        mm.feed(5);
+        mm.feed(1);
+        assert_eq!(mm.len(), 3);
+        assert_eq!(mm.window_size(), 3);
+        assert_eq!(mm.min(), Some(1));
+        assert_eq!(mm.max(), Some(5));
+        mm.feed(2);
+        mm.feed(3);
+        assert_eq!(mm.min(), Some(1));
+        assert_eq!(mm.max(), Some(3));
+        mm.feed(4);
+        assert_eq!(mm.min(), Some(2));
+        assert_eq!(mm.max(), Some(4));
+        assert_eq!(mm.range(), Some(2));
+    }
+
+    #[test]
+    fn test_range_overflow() {
+        let mut mm: MovMinMax<i8, 2> = MovMinMax::new();
+        mm.feed(-100);
+        mm.feed(100);
+        assert_eq!(mm.range(), None);
+        mm.feed(27);
+        assert_eq!(mm.range(), Some(73));
+        mm.feed(-100);
+        assert_eq!(mm.range(), Some(127));
+        mm.feed(-128);
+        assert_eq!(mm.range(), Some(28));
+        mm.feed(0);
+        assert_eq!(mm.range(), None);
+
+        let mut mm: MovMinMax<u8, 2> = MovMinMax::new();
+        mm.feed(0);
+        mm.feed(255);
+        assert_eq!(mm.range(), Some(255));
+    }
+
+    #[test]
+    fn test_f64() {
+        let mut mm: MovMinMax<f64, 2> = MovMinMax::default();
+        mm.feed(1.5);
+        mm.feed(-0.5);
+        assert_eq!(mm.min(), Some(-0.5));
+        assert_eq!(mm.max(), Some(1.5));
+        assert_eq!(mm.range(), Some(2.0));
+        mm.feed(0.25);
+        assert_eq!(mm.min(), Some(-0.5));
+        assert_eq!(mm.max(), Some(0.25));
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut mm: MovMinMax<u8, 4> = MovMinMax::new();
+        mm.feed(1);
+        mm.feed(200);
+        mm.reset();
+        assert!(mm.is_empty());
+        assert_eq!(mm.max(), None);
+        mm.feed(7);
+        assert_eq!(mm.min(), Some(7));
+        assert_eq!(mm.max(), Some(7));
+    }
+}
+
+// vim: ts=4 sw=4 expandtab
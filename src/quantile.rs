@@ -0,0 +1,354 @@
+// -*- coding: utf-8 -*-
+//
+// Copyright 2026 Michael Büsch <m@bues.ch>
+//
+// Licensed under the Apache License version 2.0
+// or the MIT license, at your option.
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//
+
+use crate::error::MovAvgError;
+use crate::median::SortedWindow;
+use num_traits::{float::FloatCore, Num, NumCast};
+
+/// Interpolation method for a quantile that lies between two items of the sorted window.
+///
+/// The quantile `q` maps to the fractional rank `q * (len - 1)` in the sorted window.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum QuantileMethod {
+    /// Use the item with the nearest rank.
+    ///
+    /// A fractional rank of exactly one half selects the item with the even rank.
+    Nearest,
+    /// Linearly interpolate between the two neighboring items.
+    ///
+    /// The distance between the two items is interpolated in `f64`
+    /// and added to the lower item in `T`.
+    /// For integers the result is rounded down.
+    Linear,
+    /// Use the lower one of the two neighboring items.
+    Lower,
+    /// Use the higher one of the two neighboring items.
+    Higher,
+}
+
+impl Default for QuantileMethod {
+    /// The default method is [QuantileMethod::Linear].
+    fn default() -> Self {
+        QuantileMethod::Linear
+    }
+}
+
+/// # Moving quantile
+///
+/// Sliding window order statistics.
+/// Any quantile (percentile) of the window can be queried with [MovQuantile::quantile].
+///
+/// A sorted view of the window is maintained alongside the ring buffer.
+/// Feeding a new value takes `O(WINDOW_SIZE)` time
+/// and querying a quantile takes `O(1)` time.
+/// All storage has a fixed size and no heap allocation is done.
+///
+/// ```
+/// use movavg::{MovQuantile, QuantileMethod};
+///
+/// let mut lat: MovQuantile<u32, 100> = MovQuantile::new(); // window size = 100
+/// for value in 1..=200 {
+///     lat.feed(value); // values 1 to 100 are evicted
+/// }
+/// assert_eq!(lat.quantile(0.0), 101);
+/// assert_eq!(lat.quantile(0.5), 150); // 150.5 truncated
+/// assert_eq!(lat.quantile(0.9), 190); // 190.1 truncated
+/// assert_eq!(lat.quantile(1.0), 200);
+///
+/// let lat = lat.with_method(QuantileMethod::Higher);
+/// assert_eq!(lat.quantile(0.5), 151);
+/// assert_eq!(lat.quantile(0.99), 200);
+/// ```
+///
+/// # Type Generics
+///
+/// `struct MovQuantile<T, WINDOW_SIZE>`
+///
+/// * `T` - The type of the `feed()` input value.
+/// * `WINDOW_SIZE` - The size of the sliding window.
+///   In number of fed elements.
+///
+/// # Float values
+///
+/// `NaN` values are not ordered with respect to other values.
+/// Feeding `NaN` leads to unspecified (but memory safe) results
+/// until the `NaN` value has been evicted from the window.
+#[derive(Clone, Debug)]
+pub struct MovQuantile<T, const WINDOW_SIZE: usize> {
+    window: SortedWindow<T, WINDOW_SIZE>,
+    method: QuantileMethod,
+}
+
+impl<T, const WINDOW_SIZE: usize> MovQuantile<T, WINDOW_SIZE>
+where
+    T: Num + NumCast + PartialOrd + Copy,
+{
+    /// Compile time check of the `WINDOW_SIZE` generic.
+    const WINDOW_SIZE_CHECK: () = assert!(WINDOW_SIZE > 0, "WINDOW_SIZE must not be zero.");
+
+    /// Construct a new moving quantile.
+    ///
+    /// The interpolation method defaults to [QuantileMethod::Linear].
+    pub fn new() -> MovQuantile<T, WINDOW_SIZE> {
+        let () = Self::WINDOW_SIZE_CHECK;
+        MovQuantile {
+            window: SortedWindow::new(),
+            method: QuantileMethod::default(),
+        }
+    }
+
+    /// Set the interpolation method.
+    pub fn with_method(mut self, method: QuantileMethod) -> Self {
+        self.method = method;
+        self
+    }
+
+    /// Get the interpolation method.
+    #[inline]
+    pub const fn method(&self) -> QuantileMethod {
+        self.method
+    }
+
+    /// Reset the moving quantile.
+    ///
+    /// This resets the number of accumulated items to 0,
+    /// as if this instance was re-created with [MovQuantile::new].
+    /// The interpolation method is kept.
+    pub fn reset(&mut self) {
+        self.window.reset();
+    }
+
+    /// Get the current length of the window.
+    ///
+    /// This length is in the range of `0..WINDOW_SIZE`.
+    /// If the length is less than `WINDOW_SIZE`, then the window
+    /// has not been fully populated, yet.
+    ///
+    /// See [Self::window_size] to get the nominal maximum length of the window.
+    #[inline]
+    pub const fn len(&self) -> usize {
+        self.window.len()
+    }
+
+    /// Check if no items have been fed, yet.
+    ///
+    /// This returns true, if [Self::len] `==0`.
+    ///
+    /// This returns false, if [Self::len] `!=0`.
+    #[inline]
+    pub const fn is_empty(&self) -> bool {
+        self.window.len() == 0
+    }
+
+    /// Get the nominal size of the window.
+    ///
+    /// This is always equal to `WINDOW_SIZE`.
+    ///
+    /// See [Self::len] to get the actual current length of the window.
+    #[inline]
+    pub const fn window_size(&self) -> usize {
+        WINDOW_SIZE
+    }
+
+    /// Feed a new value into the window.
+    ///
+    /// If the window is fully populated, then the oldest value is evicted.
+    ///
+    /// * `value` - The new value to feed into the window.
+    pub fn feed(&mut self, value: T) {
+        self.window.feed(value);
+    }
+
+    /// Try to get the quantile `q` of the window.
+    ///
+    /// * `q` - The quantile in the range `0.0..=1.0`.
+    ///   `0.0` is the minimum, `0.5` is the median and `1.0` is the maximum.
+    ///
+    /// Returns `Err`, if the internal state is empty.
+    ///
+    /// Returns `Err`, if `q` is out of range.
+    ///
+    /// Returns `Err`, if any value conversion fails.
+    pub fn try_quantile(&self, q: f64) -> Result<T, MovAvgError> {
+        if !(0.0..=1.0).contains(&q) {
+            return Err(MovAvgError::InvalidQuantile);
+        }
+        let n = self.window.len();
+        if n == 0 {
+            return Err(MovAvgError::Empty);
+        }
+
+        let rank = q * (n - 1) as f64;
+        let lower = rank as usize; // floor
+        let frac = rank - lower as f64;
+        if frac == 0.0 {
+            return Ok(self.window.sorted_item(lower));
+        }
+        let higher = lower + 1;
+
+        match self.method {
+            QuantileMethod::Nearest => {
+                if frac > 0.5 || (frac == 0.5 && lower % 2 != 0) {
+                    Ok(self.window.sorted_item(higher))
+                } else {
+                    Ok(self.window.sorted_item(lower))
+                }
+            }
+            QuantileMethod::Linear => {
+                let cast = |v: T| -> Result<f64, MovAvgError> {
+                    <f64 as NumCast>::from(v).ok_or(MovAvgError::CastInput)
+                };
+                let lo = self.window.sorted_item(lower);
+                let hi = self.window.sorted_item(higher);
+                let (lo_f, hi_f) = (cast(lo)?, cast(hi)?);
+                let value = if T::from(hi_f - lo_f).is_some() {
+                    // Interpolate the offset from the lower item in T,
+                    // so that big integers don't lose precision in f64.
+                    let offset = T::from(cast(hi - lo)? * frac).ok_or(MovAvgError::CastResult)?;
+                    lo + offset
+                } else {
+                    // The distance does not fit into the integer type T.
+                    let value = lo_f + ((hi_f - lo_f) * frac);
+                    T::from(FloatCore::floor(value)).ok_or(MovAvgError::CastResult)?
+                };
+                // Rounding errors must not leave the interval.
+                if value < lo {
+                    Ok(lo)
+                } else if value > hi {
+                    Ok(hi)
+                } else {
+                    Ok(value)
+                }
+            }
+            QuantileMethod::Lower => Ok(self.window.sorted_item(lower)),
+            QuantileMethod::Higher => Ok(self.window.sorted_item(higher)),
+        }
+    }
+
+    /// Get the quantile `q` of the window.
+    ///
+    /// * `q` - The quantile in the range `0.0..=1.0`.
+    ///   `0.0` is the minimum, `0.5` is the median and `1.0` is the maximum.
+    ///
+    /// # Panics
+    ///
+    /// Panics, if the internal state is empty, if `q` is out of range,
+    /// or if any value conversion fails.
+    pub fn quantile(&self, q: f64) -> T {
+        match self.try_quantile(q) {
+            Ok(value) => value,
+            Err(e) => panic!("MovQuantile calculation failed: {}", e),
+        }
+    }
+}
+
+impl<T, const WINDOW_SIZE: usize> Default for MovQuantile<T, WINDOW_SIZE>
+where
+    T: Num + NumCast + PartialOrd + Copy,
+{
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn feed_all<const WINDOW_SIZE: usize>(q: &mut MovQuantile<i32, WINDOW_SIZE>, values: &[i32]) {
+        for v in values {
+            q.feed(*v);
+        }
+    }
+
+    #[test]
+    fn test_methods() {
+        // Sorted window: 10, 20, 30, 40
+        let mut q: MovQuantile<i32, 4> = MovQuantile::new();
+        feed_all(&mut q, &[99, 40, 10, 30, 20]);
+        assert_eq!(q.len(), 4);
+        assert_eq!(q.method(), QuantileMethod::Linear);
+        assert_eq!(q.quantile(0.0), 10);
+        assert_eq!(q.quantile(0.5), 25);
+        assert_eq!(q.quantile(0.9), 37);
+        assert_eq!(q.quantile(1.0), 40);
+
+        let q = q.with_method(QuantileMethod::Lower);
+        assert_eq!(q.quantile(0.5), 20);
+        assert_eq!(q.quantile(0.9), 30);
+        let q = q.with_method(QuantileMethod::Higher);
+        assert_eq!(q.quantile(0.1), 20);
+        assert_eq!(q.quantile(0.5), 30);
+        let q = q.with_method(QuantileMethod::Nearest);
+        assert_eq!(q.quantile(0.1), 10);
+        assert_eq!(q.quantile(0.3), 20);
+        assert_eq!(q.quantile(0.5), 30); // rank 1.5 -> even rank 2
+        assert_eq!(q.quantile(0.5 / 3.0), 10); // rank 0.5 -> even rank 0
+    }
+
+    #[test]
+    fn test_f64() {
+        let mut q: MovQuantile<f64, 3> = MovQuantile::default();
+        q.feed(1.0);
+        assert_eq!(q.quantile(0.3), 1.0);
+        q.feed(2.0);
+        assert_eq!(q.quantile(0.25), 1.25);
+        q.feed(4.0);
+        assert_eq!(q.quantile(0.75), 3.0);
+        q.feed(0.0);
+        assert_eq!(q.quantile(0.5), 2.0);
+    }
+
+    #[test]
+    fn test_linear_big_int() {
+        let mut q: MovQuantile<u64, 2> = MovQuantile::new();
+        q.feed(u64::MAX - 1);
+        q.feed(u64::MAX);
+        assert_eq!(q.try_quantile(0.5), Ok(u64::MAX - 1));
+        assert_eq!(q.try_quantile(1.0), Ok(u64::MAX));
+
+        q.feed((1 << 60) + 1);
+        q.feed((1 << 60) + 3);
+        assert_eq!(q.try_quantile(0.5), Ok((1 << 60) + 2));
+
+        let mut q: MovQuantile<i8, 2> = MovQuantile::new();
+        q.feed(i8::MIN);
+        q.feed(i8::MAX);
+        assert_eq!(q.try_quantile(0.5), Ok(-1)); // -0.5 rounded down
+        assert_eq!(q.try_quantile(1.0), Ok(i8::MAX));
+        q.feed(-3);
+        q.feed(-2);
+        assert_eq!(q.try_quantile(0.5), Ok(-3)); // -2.5 rounded down
+    }
+
+    #[test]
+    fn test_errors() {
+        let mut q: MovQuantile<u8, 3> = MovQuantile::new();
+        assert_eq!(q.try_quantile(0.5), Err(MovAvgError::Empty));
+        q.feed(1);
+        assert_eq!(q.try_quantile(-0.1), Err(MovAvgError::InvalidQuantile));
+        assert_eq!(q.try_quantile(1.1), Err(MovAvgError::InvalidQuantile));
+        assert_eq!(q.try_quantile(f64::NAN), Err(MovAvgError::InvalidQuantile));
+        assert_eq!(q.try_quantile(1.0), Ok(1));
+        q.reset();
+        assert!(q.is_empty());
+        assert_eq!(q.window_size(), 3);
+    }
+
+    #[test]
+    #[should_panic(expected = "The quantile is out of range.")]
+    fn test_panic() {
+        let mut q: MovQuantile<u8, 3> = MovQuantile::new();
+        q.feed(1);
+        q.quantile(2.0);
+    }
+}
+
+// vim: ts=4 sw=4 expandtab
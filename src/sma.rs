@@ -7,26 +7,330 @@
 // SPDX-License-Identifier: Apache-2.0 OR MIT
 //
 
-use num_traits::{Num, NumCast};
+use crate::{error::MovAvgError, wide::WideAccu};
+use num_traits::{float::FloatCore, Bounded, CheckedAdd, CheckedMul, Num, NumCast};
 
 /// Initialize the accumulator from scratch by summing up all items from the window buffer.
 #[inline]
-fn initialize_accu<T, A>(window_buffer: &[T]) -> Result<A, &'static str>
+fn initialize_accu<T, A>(window_buffer: &[T]) -> Result<A, MovAvgError>
 where
     T: Num + NumCast + Copy,
-    A: Num + NumCast + Copy,
+    A: Num + NumCast + Copy + MovAvgAccu<T>,
+{
+    initialize_accu_with(window_buffer, OverflowPolicy::Checked)
+}
+
+/// Initialize the accumulator from scratch by summing up all items from the window buffer
+/// with the overflow `policy`.
+#[inline]
+fn initialize_accu_with<T, A>(window_buffer: &[T], policy: OverflowPolicy) -> Result<A, MovAvgError>
+where
+    T: Num + NumCast + Copy,
+    A: Num + NumCast + Copy + MovAvgAccu<T>,
 {
     let mut accu = A::zero();
     for value in window_buffer {
         if let Some(value) = A::from(*value) {
-            accu = accu + value;
+            accu = accu.add_accu_with(value, policy)?;
         } else {
-            return Err("Failed to cast value to accumulator type.");
+            return Err(MovAvgError::CastInput);
         }
     }
     Ok(accu)
 }
 
+/// Initialize the accumulator and the compensation term from scratch
+/// by summing up all items from the window buffer with compensated summation.
+#[inline]
+fn initialize_accu_compensated<T, A>(
+    window_buffer: &[T],
+    policy: OverflowPolicy,
+) -> Result<(A, A), MovAvgError>
+where
+    T: Num + NumCast + Copy,
+    A: Num + NumCast + Copy + MovAvgAccu<T>,
+{
+    let mut accu = A::zero();
+    let mut comp = A::zero();
+    for (i, value) in window_buffer.iter().enumerate() {
+        if let Some(value) = A::from(*value) {
+            (accu, comp) =
+                accu.recalc_accu_compensated(comp, A::zero(), value, &window_buffer[..=i], policy)?;
+        } else {
+            return Err(MovAvgError::CastInput);
+        }
+    }
+    Ok((accu, comp))
+}
+
+/// Add `value` to `sum` with Neumaier compensated summation.
+///
+/// Returns the new sum and the new compensation term.
+#[inline]
+pub(crate) fn neumaier_add<F: FloatCore>(sum: F, comp: F, value: F) -> (F, F) {
+    let new_sum = sum + value;
+    let new_comp = if sum.abs() >= value.abs() {
+        comp + ((sum - new_sum) + value)
+    } else {
+        comp + ((value - new_sum) + sum)
+    };
+    (new_sum, new_comp)
+}
+
+/// Accumulation strategy of the Simple Moving Average.
+///
+/// The strategy is selected per instance, e.g. with [MovAvg::with_accu_mode].
+///
+/// Integer accumulators are always exact and incremental (`O(1)` per feed),
+/// regardless of the strategy.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum AccuMode {
+    /// Exact recalculation.
+    ///
+    /// Float accumulators are recalculated from scratch on every feed (`O(N)` per feed).
+    /// This avoids accumulating rounding errors over time.
+    Exact,
+    /// Incremental update.
+    ///
+    /// Float accumulators are updated incrementally (`O(1)` per feed)
+    /// by subtracting the oldest value and adding the new value.
+    /// This accumulates rounding and cancellation errors over time.
+    Incremental,
+    /// Incremental update with periodic resynchronization.
+    ///
+    /// Float accumulators are updated incrementally (`O(1)` per feed)
+    /// and recalculated from scratch every `K` feeds.
+    /// This gives amortized `O(N / K)` cost per feed with bounded rounding errors.
+    ///
+    /// The value is the resynchronization interval `K` in number of feeds.
+    /// An interval of `0` or `1` recalculates on every feed, like [AccuMode::Exact].
+    Resync(usize),
+    /// Neumaier compensated summation.
+    ///
+    /// Float accumulators are updated incrementally (`O(1)` per feed)
+    /// and a compensation term for the rounding error is kept alongside the accumulator.
+    /// This gives near-exact results at `O(1)` cost.
+    Compensated,
+}
+
+impl Default for AccuMode {
+    /// The default strategy is [AccuMode::Exact].
+    ///
+    /// If the `fastfloat` cargo feature is enabled,
+    /// then the default strategy is [AccuMode::Incremental].
+    fn default() -> Self {
+        if cfg!(feature = "fastfloat") {
+            AccuMode::Incremental
+        } else {
+            AccuMode::Exact
+        }
+    }
+}
+
+/// Rounding mode of integer average results.
+///
+/// The rounding mode is selected per instance, e.g. with [MovAvg::with_rounding].
+///
+/// Float averages are not affected by the rounding mode.
+/// Averages of float accumulators are rounded according to the rounding mode,
+/// if they are converted to an integer type.
+/// That is the case for [MovAvg::feed] and [MovAvg::get] with an integer item type
+/// and for [MovAvg::get_as] with an integer output type.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Rounding {
+    /// Round towards zero.
+    Truncate,
+    /// Round towards negative infinity.
+    Floor,
+    /// Round towards positive infinity.
+    Ceil,
+    /// Round to the nearest integer.
+    /// Round halfway cases towards positive infinity.
+    HalfUp,
+    /// Round to the nearest integer.
+    /// Round halfway cases to the nearest even integer.
+    HalfEven,
+}
+
+impl Default for Rounding {
+    /// The default rounding mode is [Rounding::Truncate].
+    fn default() -> Self {
+        Rounding::Truncate
+    }
+}
+
+/// Check if the item type `T` can represent fractions.
+#[inline]
+fn is_fractional<T: Num + NumCast>() -> bool {
+    T::from(0.5_f64).map_or(false, |half| half != T::zero())
+}
+
+/// Get the machine epsilon of the float accumulator type `A`.
+#[inline]
+fn float_epsilon<A: NumCast>() -> f64 {
+    // An f32 can't represent 1 + f64::EPSILON.
+    let one = A::from(1.0 + f64::EPSILON).and_then(|one| <f64 as NumCast>::from(one));
+    if one == Some(1.0) {
+        f32::EPSILON as f64
+    } else {
+        f64::EPSILON
+    }
+}
+
+/// Divide the integer `dividend` by the positive integer `divisor`
+/// and round the result according to `rounding`.
+#[inline]
+fn int_div_round<A>(dividend: A, divisor: A, rounding: Rounding) -> A
+where
+    A: Num + PartialOrd + Copy,
+{
+    let zero = A::zero();
+    let one = A::one();
+    let quot = dividend / divisor;
+    let rem = dividend % divisor;
+    if rem == zero {
+        return quot;
+    }
+    // The remainder has the sign of the dividend and its magnitude is less than the divisor.
+    // Compare the magnitude of the remainder to the distance to the next integer.
+    let (neg, rem_abs) = if rem < zero {
+        (true, zero - rem)
+    } else {
+        (false, rem)
+    };
+    let rem_other = divisor - rem_abs;
+    let away = match rounding {
+        Rounding::Truncate => false,
+        Rounding::Floor => neg,
+        Rounding::Ceil => !neg,
+        Rounding::HalfUp => rem_abs > rem_other || (rem_abs == rem_other && !neg),
+        Rounding::HalfEven => {
+            rem_abs > rem_other || (rem_abs == rem_other && quot % (one + one) != zero)
+        }
+    };
+    match (away, neg) {
+        (false, _) => quot,
+        (true, false) => quot + one,
+        (true, true) => quot - one,
+    }
+}
+
+/// Calculate the average of the integer items in `window_buffer` without overflow
+/// and round it according to `rounding`.
+///
+/// Returns the average, the sum of the items clamped to the limits of `A`
+/// and whether the sum has been clamped.
+fn int_saturating_avg<T, A>(
+    window_buffer: &[T],
+    rounding: Rounding,
+) -> Result<(A, A, bool), MovAvgError>
+where
+    T: Num + NumCast + Copy,
+    A: Num + NumCast + PartialOrd + Bounded + CheckedAdd + CheckedMul + Copy,
+{
+    let zero = A::zero();
+    let one = A::one();
+    let divisor = A::from(window_buffer.len()).ok_or(MovAvgError::CastCount)?;
+    if divisor == zero {
+        return Err(MovAvgError::Empty);
+    }
+    // Sum up the quotients and the remainders of the items divided by the number of items.
+    // The average is quot + rem / divisor with 0 <= rem < divisor.
+    let mut quot = zero;
+    let mut rem = zero;
+    for value in window_buffer {
+        let value = A::from(*value).ok_or(MovAvgError::CastInput)?;
+        let (mut q, mut r) = (value / divisor, value % divisor);
+        if r < zero {
+            q = q - one;
+            r = r + divisor;
+        }
+        // Carry, if rem + r >= divisor. This is calculated without overflow.
+        if r >= divisor - rem {
+            rem = r - (divisor - rem);
+            q = q + one;
+        } else {
+            rem = rem + r;
+        }
+        quot = quot.checked_add(&q).ok_or(MovAvgError::AccuOverflow)?;
+    }
+    let rem_other = divisor - rem;
+    let up = rem != zero
+        && match rounding {
+            Rounding::Truncate => quot < zero,
+            Rounding::Floor => false,
+            Rounding::Ceil => true,
+            Rounding::HalfUp => rem >= rem_other,
+            Rounding::HalfEven => {
+                rem > rem_other || (rem == rem_other && quot % (one + one) != zero)
+            }
+        };
+    let avg = if up { quot + one } else { quot };
+    // The sum is quot * divisor + rem.
+    // For negative sums calculate (quot + 1) * divisor - (divisor - rem),
+    // so that the intermediate product does not overflow, if the sum fits.
+    let sum = if quot < zero {
+        (quot + one)
+            .checked_mul(&divisor)
+            .and_then(|sum| sum.checked_add(&(zero - rem_other)))
+    } else {
+        quot.checked_mul(&divisor)
+            .and_then(|sum| sum.checked_add(&rem))
+    };
+    match sum {
+        Some(sum) => Ok((avg, sum, false)),
+        None if quot < zero => Ok((avg, A::min_value(), true)),
+        None => Ok((avg, A::max_value(), true)),
+    }
+}
+
+/// Overflow handling of integer accumulators.
+///
+/// The overflow policy is selected per instance, e.g. with [MovAvg::with_overflow_policy].
+///
+/// Float accumulators are not affected by the overflow policy.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum OverflowPolicy {
+    /// An accumulator overflow is an error.
+    ///
+    /// `try_feed` returns [MovAvgError::AccuOverflow] and `feed` panics.
+    /// The Moving Average state is not modified in this case.
+    Checked,
+    /// The sum is clamped to the limits of the accumulator type.
+    ///
+    /// The average is always exact, even if the sum of the window
+    /// does not fit into the accumulator.
+    /// While the sum does not fit, the average is calculated from the window buffer
+    /// without overflow and the sum returned by [MovAvg::sum] is clamped.
+    ///
+    /// # Performance
+    ///
+    /// While the sum does not fit into the accumulator,
+    /// every feed and every query costs `O(N)` instead of `O(1)`,
+    /// where `N` is the window size.
+    /// Feeding a saturated window of 1000 items is about
+    /// three orders of magnitude slower than feeding with [OverflowPolicy::Checked].
+    /// See `benches/saturating.rs`.
+    /// If the sum regularly does not fit, then use a bigger accumulator type instead.
+    Saturating,
+    /// The accumulator wraps around on overflow (modular arithmetic).
+    ///
+    /// The average is exact as long as the sum of the current window
+    /// fits into the accumulator.
+    /// Overflows of intermediate sums do not matter.
+    Wrapping,
+}
+
+impl Default for OverflowPolicy {
+    /// The default overflow policy is [OverflowPolicy::Checked].
+    fn default() -> Self {
+        OverflowPolicy::Checked
+    }
+}
+
 /// Internal accumulator calculation trait for integers and floats.
 ///
 /// This usually does *not* have to be implemented by the library user.
@@ -36,26 +340,227 @@ where
 ///
 /// `T` is the SMA input value type.
 pub trait MovAvgAccu<T>: Copy {
+    /// Add `value` to the accumulator.
+    ///
+    /// The default implementation calls [MovAvgAccu::recalc_accu]
+    /// with a zero `first_value`.
+    #[inline]
+    fn add_accu(self, value: Self) -> Result<Self, MovAvgError>
+    where
+        Self: Num,
+    {
+        self.recalc_accu(Self::zero(), value, &[])
+    }
+
+    /// Remove `first_value` from the accumulator and add `input_value`.
+    ///
+    /// This is the incremental update of the accumulator.
     fn recalc_accu(
         self,
         first_value: Self,
         input_value: Self,
         window_buffer: &[T],
-    ) -> Result<Self, &'static str>;
+    ) -> Result<Self, MovAvgError>;
+
+    /// Add `value` to the accumulator with the overflow `policy`.
+    ///
+    /// The default implementation calls [MovAvgAccu::add_accu] and ignores `policy`.
+    /// This is correct for floats.
+    #[inline]
+    fn add_accu_with(self, value: Self, policy: OverflowPolicy) -> Result<Self, MovAvgError>
+    where
+        Self: Num,
+    {
+        let _ = policy;
+        self.add_accu(value)
+    }
+
+    /// Remove `first_value` from the accumulator and add `input_value`
+    /// with the overflow `policy`.
+    ///
+    /// `window_buffer` contains all items of the window, including `input_value`.
+    ///
+    /// The default implementation calls [MovAvgAccu::recalc_accu] and ignores `policy`.
+    /// This is correct for floats.
+    #[inline]
+    fn recalc_accu_with(
+        self,
+        first_value: Self,
+        input_value: Self,
+        window_buffer: &[T],
+        policy: OverflowPolicy,
+    ) -> Result<Self, MovAvgError> {
+        let _ = policy;
+        self.recalc_accu(first_value, input_value, window_buffer)
+    }
+
+    /// Remove `first_value` from the accumulator and add `input_value`
+    /// without accumulating rounding errors.
+    ///
+    /// `window_buffer` contains all items of the window, including `input_value`.
+    ///
+    /// The default implementation calls [MovAvgAccu::recalc_accu_with].
+    /// This is correct for exact accumulators such as integers.
+    #[inline]
+    fn recalc_accu_exact(
+        self,
+        first_value: Self,
+        input_value: Self,
+        window_buffer: &[T],
+        policy: OverflowPolicy,
+    ) -> Result<Self, MovAvgError> {
+        self.recalc_accu_with(first_value, input_value, window_buffer, policy)
+    }
+
+    /// Remove `first_value` from the accumulator and add `input_value`
+    /// with compensated summation.
+    ///
+    /// `comp` is the current compensation term.
+    /// The compensated sum is `accu + comp`.
+    ///
+    /// Returns the new accumulator and the new compensation term.
+    ///
+    /// The default implementation calls [MovAvgAccu::recalc_accu_with]
+    /// and keeps the compensation term unchanged.
+    /// This is correct for exact accumulators such as integers.
+    #[inline]
+    fn recalc_accu_compensated(
+        self,
+        comp: Self,
+        first_value: Self,
+        input_value: Self,
+        window_buffer: &[T],
+        policy: OverflowPolicy,
+    ) -> Result<(Self, Self), MovAvgError> {
+        Ok((
+            self.recalc_accu_with(first_value, input_value, window_buffer, policy)?,
+            comp,
+        ))
+    }
+
+    /// Divide the accumulator by the number of items `divisor`
+    /// and round the result according to `rounding`.
+    ///
+    /// The default implementation does a plain division and ignores `rounding`.
+    /// This is correct for floats.
+    #[inline]
+    fn div_accu(self, divisor: Self, rounding: Rounding) -> Self
+    where
+        Self: Num,
+    {
+        let _ = rounding;
+        self / divisor
+    }
+
+    /// Check if the accumulator type can represent fractions.
+    ///
+    /// The default implementation returns `false`.
+    /// This is correct for integers.
+    #[inline]
+    fn is_float() -> bool {
+        false
+    }
+
+    /// Round the accumulator to an integral value according to `rounding`.
+    ///
+    /// The default implementation returns the accumulator unchanged.
+    /// This is correct for integers.
+    #[inline]
+    fn round_accu(self, rounding: Rounding) -> Self {
+        let _ = rounding;
+        self
+    }
+
+    /// Calculate the average of all items in `window_buffer`
+    /// without overflowing the accumulator.
+    ///
+    /// Returns the average rounded according to `rounding`,
+    /// the sum clamped to the limits of the accumulator type
+    /// and whether the sum has been clamped.
+    ///
+    /// The default implementation returns [MovAvgError::AccuOverflow].
+    /// This is correct for floats, which are not clamped.
+    #[inline]
+    fn saturating_avg(
+        window_buffer: &[T],
+        rounding: Rounding,
+    ) -> Result<(Self, Self, bool), MovAvgError> {
+        let _ = (window_buffer, rounding);
+        Err(MovAvgError::AccuOverflow)
+    }
 }
 
 macro_rules! impl_int_accu {
     ($($t:ty),*) => {
         $(
-            impl<T> MovAvgAccu<T> for $t {
+            impl<T> MovAvgAccu<T> for $t
+            where
+                T: Num + NumCast + Copy
+            {
+                #[inline]
+                fn add_accu(self, value: Self) -> Result<Self, MovAvgError> {
+                    self.checked_add(value).ok_or(MovAvgError::AccuOverflow)
+                }
+
                 #[inline]
                 fn recalc_accu(self,
                                first_value: Self,
                                input_value: Self,
-                               _window_buffer: &[T]) -> Result<Self, &'static str> {
+                               window_buffer: &[T]) -> Result<Self, MovAvgError> {
+                    self.recalc_accu_with(first_value, input_value, window_buffer,
+                                          OverflowPolicy::Checked)
+                }
+
+                #[inline]
+                fn add_accu_with(self, value: Self, policy: OverflowPolicy) -> Result<Self, MovAvgError> {
+                    match policy {
+                        OverflowPolicy::Checked => MovAvgAccu::<T>::add_accu(self, value),
+                        // A saturated sum is tracked separately from the accumulator.
+                        // The accumulator itself wraps around.
+                        OverflowPolicy::Saturating | OverflowPolicy::Wrapping => {
+                            Ok(self.wrapping_add(value))
+                        }
+                    }
+                }
+
+                #[inline]
+                fn recalc_accu_with(self,
+                                    first_value: Self,
+                                    input_value: Self,
+                                    _window_buffer: &[T],
+                                    policy: OverflowPolicy) -> Result<Self, MovAvgError> {
                     // Subtract the to be removed value from the sum and add the new value.
-                    (self - first_value).checked_add(input_value)
-                        .ok_or("Accumulator type add overflow.")
+                    // The intermediate result of one order of operations may overflow,
+                    // even if the final result fits. Therefore, try both orders.
+                    let checked = || {
+                        if first_value == input_value {
+                            Some(self)
+                        } else {
+                            self.checked_sub(first_value)
+                                .and_then(|accu| accu.checked_add(input_value))
+                                .or_else(|| {
+                                    self.checked_add(input_value)
+                                        .and_then(|accu| accu.checked_sub(first_value))
+                                })
+                        }
+                    };
+                    match policy {
+                        OverflowPolicy::Checked => checked().ok_or(MovAvgError::AccuOverflow),
+                        OverflowPolicy::Saturating | OverflowPolicy::Wrapping => {
+                            Ok(self.wrapping_sub(first_value).wrapping_add(input_value))
+                        }
+                    }
+                }
+
+                #[inline]
+                fn div_accu(self, divisor: Self, rounding: Rounding) -> Self {
+                    int_div_round(self, divisor, rounding)
+                }
+
+                #[inline]
+                fn saturating_avg(window_buffer: &[T],
+                                  rounding: Rounding) -> Result<(Self, Self, bool), MovAvgError> {
+                    int_saturating_avg(window_buffer, rounding)
                 }
             }
         )*
@@ -69,17 +574,58 @@ macro_rules! impl_float_accu {
             where
                 T: Num + NumCast + Copy
             {
+                #[inline]
+                fn add_accu(self, value: Self) -> Result<Self, MovAvgError> {
+                    Ok(self + value)
+                }
+
                 #[inline]
                 fn recalc_accu(self,
                                first_value: Self,
                                input_value: Self,
-                               window_buffer: &[T]) -> Result<Self, &'static str> {
-                    if cfg!(feature="fastfloat") {
-                        // Fast calculation, just like the integer variant.
-                        Ok((self - first_value) + input_value)
-                    } else {
-                        // Recalculate the accumulator from scratch.
-                        initialize_accu(window_buffer)
+                               _window_buffer: &[T]) -> Result<Self, MovAvgError> {
+                    // Fast calculation, just like the integer variant.
+                    Ok((self - first_value) + input_value)
+                }
+
+                #[inline]
+                fn recalc_accu_exact(self,
+                                     _first_value: Self,
+                                     _input_value: Self,
+                                     window_buffer: &[T],
+                                     _policy: OverflowPolicy) -> Result<Self, MovAvgError> {
+                    // Recalculate the accumulator from scratch.
+                    initialize_accu(window_buffer)
+                }
+
+                #[inline]
+                fn recalc_accu_compensated(self,
+                                           comp: Self,
+                                           first_value: Self,
+                                           input_value: Self,
+                                           _window_buffer: &[T],
+                                           _policy: OverflowPolicy) -> Result<(Self, Self), MovAvgError> {
+                    let (accu, comp) = neumaier_add(self, comp, -first_value);
+                    Ok(neumaier_add(accu, comp, input_value))
+                }
+
+                #[inline]
+                fn is_float() -> bool {
+                    true
+                }
+
+                #[inline]
+                fn round_accu(self, rounding: Rounding) -> Self {
+                    let floor = FloatCore::floor(self);
+                    let diff = self - floor;
+                    match rounding {
+                        Rounding::Truncate => FloatCore::trunc(self),
+                        Rounding::Floor => floor,
+                        Rounding::Ceil => FloatCore::ceil(self),
+                        Rounding::HalfUp if diff >= 0.5 => floor + 1.0,
+                        Rounding::HalfEven if diff > 0.5
+                            || (diff == 0.5 && FloatCore::floor(floor / 2.0) * 2.0 != floor) => floor + 1.0,
+                        Rounding::HalfUp | Rounding::HalfEven => floor,
                     }
                 }
             }
@@ -87,12 +633,1016 @@ macro_rules! impl_float_accu {
     }
 }
 
-impl_int_accu!(i8, i16, i32, i64, isize, u8, u16, u32, u64, usize);
+impl_int_accu!(i8, i16, i32, i64, isize, u8, u16, u32, u64, usize);
+
+#[cfg(has_i128)]
+impl_int_accu!(i128, u128);
+
+impl_float_accu!(f32, f64);
+
+/// Storage independent Simple Moving Average state.
+///
+/// This contains the Moving Average calculation logic
+/// that is shared by all window buffer storage variants.
+#[derive(Clone, Debug)]
+pub(crate) struct SmaState<A> {
+    pub(crate) accu: A,
+    pub(crate) comp: A,
+    pub(crate) mode: AccuMode,
+    pub(crate) policy: OverflowPolicy,
+    pub(crate) saturated: bool,
+    pub(crate) rounding: Rounding,
+    pub(crate) nr_since_resync: usize,
+    pub(crate) nr_items: usize,
+    pub(crate) index: usize,
+}
+
+impl<A> SmaState<A>
+where
+    A: Num + NumCast + Copy,
+{
+    /// Initialize the state from a (partially) pre-populated window buffer.
+    ///
+    /// See [MovAvg::new_init] for a description of the arguments.
+    pub(crate) fn try_new_init<T>(
+        buffer: &[T],
+        nr_populated: usize,
+    ) -> Result<SmaState<A>, MovAvgError>
+    where
+        T: Num + NumCast + Copy,
+        A: MovAvgAccu<T>,
+    {
+        let size = buffer.len();
+        if size == 0 {
+            return Err(MovAvgError::InvalidWindowSize);
+        }
+
+        let nr_items = nr_populated;
+        if nr_items > size {
+            return Err(MovAvgError::InvalidNrPopulated);
+        }
+
+        let index = nr_items % size;
+
+        let mut state = SmaState {
+            accu: A::zero(),
+            comp: A::zero(),
+            mode: AccuMode::default(),
+            policy: OverflowPolicy::default(),
+            saturated: false,
+            rounding: Rounding::default(),
+            nr_since_resync: 0,
+            nr_items,
+            index,
+        };
+        state.reconfigure(buffer, state.mode, state.policy)?;
+        Ok(state)
+    }
+
+    /// Initialize the state from a (partially) pre-populated window buffer.
+    ///
+    /// Panics, if the initialization fails.
+    pub(crate) fn new_init<T>(buffer: &[T], nr_populated: usize) -> SmaState<A>
+    where
+        T: Num + NumCast + Copy,
+        A: MovAvgAccu<T>,
+    {
+        Self::try_new_init(buffer, nr_populated).expect("Failed to initialize the MovAvg.")
+    }
+
+    /// Re-initialize the state from a (partially) pre-populated window buffer.
+    ///
+    /// The configuration (accumulation strategy, overflow policy and rounding mode) is kept.
+    #[cfg(feature = "alloc")]
+    pub(crate) fn reinit<T>(&mut self, buffer: &[T], nr_populated: usize) -> Result<(), MovAvgError>
+    where
+        T: Num + NumCast + Copy,
+        A: MovAvgAccu<T>,
+    {
+        let size = buffer.len();
+        if size == 0 {
+            return Err(MovAvgError::InvalidWindowSize);
+        }
+        if nr_populated > size {
+            return Err(MovAvgError::InvalidNrPopulated);
+        }
+
+        let mut state = self.clone();
+        state.nr_items = nr_populated;
+        state.index = nr_populated % size;
+        state.reconfigure(buffer, self.mode, self.policy)?;
+        *self = state;
+        Ok(())
+    }
+
+    /// Check that the state is consistent with the window buffer.
+    ///
+    /// The accumulator must match the sum of the populated items in the window buffer.
+    /// Integer accumulators must match exactly.
+    /// Float accumulators must match within a relative tolerance,
+    /// because they may carry rounding errors depending on the accumulation strategy.
+    ///
+    /// Returns the validated state with the derived saturation state.
+    pub(crate) fn validate<T>(mut self, buffer: &[T]) -> Result<Self, MovAvgError>
+    where
+        T: Num + NumCast + Copy,
+        A: MovAvgAccu<T>,
+    {
+        let size = buffer.len();
+        if size == 0 {
+            return Err(MovAvgError::InvalidWindowSize);
+        }
+        if self.nr_items > size {
+            return Err(MovAvgError::InvalidNrPopulated);
+        }
+        let index_ok = if self.nr_items < size {
+            // The populated items begin at index 0.
+            self.index == self.nr_items
+        } else {
+            self.index < size
+        };
+        let resync_ok = match self.mode {
+            AccuMode::Resync(interval) => self.nr_since_resync < interval.max(1),
+            _ => self.nr_since_resync == 0,
+        };
+        if !index_ok || !resync_ok {
+            return Err(MovAvgError::InvalidState);
+        }
+
+        let mut expected = self.clone();
+        expected.reconfigure(buffer, self.mode, self.policy)?;
+
+        let accu_ok = if A::is_float() {
+            let cast = |v: A| <f64 as NumCast>::from(v).ok_or(MovAvgError::CastInput);
+            let got = cast(self.accu + self.comp)?;
+            let exp = cast(expected.accu + expected.comp)?;
+            let tolerance = if self.mode == AccuMode::Exact {
+                // The exact accumulator is recalculated in the same order.
+                0.0
+            } else {
+                // The incremental accumulator may deviate from the recalculated sum
+                // by the rounding errors of the summation.
+                let mut magnitude = 0.0;
+                for value in &buffer[..self.nr_items] {
+                    let value = <f64 as NumCast>::from(*value).ok_or(MovAvgError::CastInput)?;
+                    magnitude += value.abs();
+                }
+                self.nr_items as f64 * float_epsilon::<A>() * magnitude
+            };
+            got == exp || (got - exp).abs() <= tolerance || (got.is_nan() && exp.is_nan())
+        } else {
+            self.accu == expected.accu && self.comp == expected.comp
+        };
+        if accu_ok {
+            self.saturated = expected.saturated;
+            Ok(self)
+        } else {
+            Err(MovAvgError::InvalidState)
+        }
+    }
+
+    /// Get the accumulation strategy.
+    #[inline]
+    pub(crate) const fn accu_mode(&self) -> AccuMode {
+        self.mode
+    }
+
+    /// Set the accumulation strategy
+    /// and recalculate the accumulator from the populated items in the window buffer.
+    pub(crate) fn set_accu_mode<T>(
+        &mut self,
+        buffer: &[T],
+        mode: AccuMode,
+    ) -> Result<(), MovAvgError>
+    where
+        T: Num + NumCast + Copy,
+        A: MovAvgAccu<T>,
+    {
+        self.reconfigure(buffer, mode, self.policy)
+    }
+
+    /// Get the overflow policy.
+    #[inline]
+    pub(crate) const fn overflow_policy(&self) -> OverflowPolicy {
+        self.policy
+    }
+
+    /// Set the overflow policy
+    /// and recalculate the accumulator from the populated items in the window buffer.
+    pub(crate) fn set_overflow_policy<T>(
+        &mut self,
+        buffer: &[T],
+        policy: OverflowPolicy,
+    ) -> Result<(), MovAvgError>
+    where
+        T: Num + NumCast + Copy,
+        A: MovAvgAccu<T>,
+    {
+        self.reconfigure(buffer, self.mode, policy)
+    }
+
+    /// Set the accumulation strategy and the overflow policy
+    /// and recalculate the accumulator from the populated items in the window buffer.
+    fn reconfigure<T>(
+        &mut self,
+        buffer: &[T],
+        mode: AccuMode,
+        policy: OverflowPolicy,
+    ) -> Result<(), MovAvgError>
+    where
+        T: Num + NumCast + Copy,
+        A: MovAvgAccu<T>,
+    {
+        let populated = &buffer[0..self.nr_items];
+        let (accu, comp) = match mode {
+            AccuMode::Exact | AccuMode::Incremental | AccuMode::Resync(_) => {
+                (initialize_accu_with(populated, policy)?, A::zero())
+            }
+            AccuMode::Compensated => initialize_accu_compensated(populated, policy)?,
+        };
+        self.accu = accu;
+        self.comp = comp;
+        self.mode = mode;
+        self.policy = policy;
+        self.saturated = Self::check_saturated(populated, policy);
+        self.nr_since_resync = 0;
+        Ok(())
+    }
+
+    /// Check if the sum of all items in `window_buffer` does not fit into the accumulator
+    /// and has to be saturated.
+    #[inline]
+    fn check_saturated<T>(window_buffer: &[T], policy: OverflowPolicy) -> bool
+    where
+        T: Num + NumCast + Copy,
+        A: MovAvgAccu<T>,
+    {
+        policy == OverflowPolicy::Saturating
+            && !A::is_float()
+            && initialize_accu::<T, A>(window_buffer).is_err()
+            && matches!(
+                A::saturating_avg(window_buffer, Rounding::default()),
+                Ok((_, _, true))
+            )
+    }
+
+    /// Get the rounding mode.
+    #[inline]
+    pub(crate) const fn rounding(&self) -> Rounding {
+        self.rounding
+    }
+
+    /// Set the rounding mode.
+    #[inline]
+    pub(crate) fn set_rounding(&mut self, rounding: Rounding) {
+        self.rounding = rounding;
+    }
+
+    /// Reset the accumulator and the number of accumulated items to 0.
+    pub(crate) fn reset(&mut self) {
+        self.accu = A::zero();
+        self.comp = A::zero();
+        self.saturated = false;
+        self.nr_since_resync = 0;
+        self.nr_items = 0;
+        self.index = 0;
+    }
+
+    /// Get the current length of the Moving Average window.
+    #[inline]
+    pub(crate) const fn len(&self) -> usize {
+        self.nr_items
+    }
+
+    /// Split the populated items of the window buffer into the two contiguous ring halves.
+    ///
+    /// The first slice holds the older items and the second slice holds the newer items.
+    pub(crate) fn as_slices<'a, T>(&self, buffer: &'a [T]) -> (&'a [T], &'a [T]) {
+        if self.nr_items >= buffer.len() {
+            // The window is fully populated and the oldest item is at self.index.
+            let (newer, older) = buffer.split_at(self.index);
+            (older, newer)
+        } else {
+            // The populated items begin at index 0.
+            (&buffer[..self.nr_items], &[])
+        }
+    }
+
+    /// Feed a new value into the window buffer and return the new average.
+    pub(crate) fn try_feed<T>(&mut self, buffer: &mut [T], value: T) -> Result<T, MovAvgError>
+    where
+        T: Num + NumCast + Copy,
+        A: MovAvgAccu<T>,
+    {
+        self.try_feed_with(buffer, value, |state, window_buffer| {
+            state.try_get(window_buffer)
+        })
+    }
+
+    /// Feed all `values` into the window buffer and return the final average.
+    ///
+    /// Each value is fed individually, as with [Self::try_feed],
+    /// but the intermediate averages are not calculated.
+    /// If an error occurs, then feeding stops at the failing value.
+    pub(crate) fn try_feed_iter<T, I>(
+        &mut self,
+        buffer: &mut [T],
+        values: I,
+    ) -> Result<T, MovAvgError>
+    where
+        T: Num + NumCast + Copy,
+        A: MovAvgAccu<T>,
+        I: IntoIterator<Item = T>,
+    {
+        for value in values {
+            self.try_feed_with(buffer, value, |_, _| Ok(()))?;
+        }
+        self.try_get(buffer)
+    }
+
+    /// Feed all `values` into the window buffer and write all intermediate averages to `out`.
+    ///
+    /// `out` must be at least as long as `values`.
+    /// Each value is fed individually with [Self::try_feed].
+    /// If an error occurs, then feeding stops at the failing value.
+    pub(crate) fn try_feed_slice_into<T>(
+        &mut self,
+        buffer: &mut [T],
+        values: &[T],
+        out: &mut [T],
+    ) -> Result<(), MovAvgError>
+    where
+        T: Num + NumCast + Copy,
+        A: MovAvgAccu<T>,
+    {
+        if out.len() < values.len() {
+            return Err(MovAvgError::BufferTooSmall);
+        }
+        for (value, avg) in values.iter().zip(out.iter_mut()) {
+            *avg = self.try_feed(buffer, *value)?;
+        }
+        Ok(())
+    }
+
+    /// Feed a new value into the window buffer and recalculate the accumulator.
+    ///
+    /// `result` is called with the new state and the new window buffer
+    /// to calculate the return value.
+    /// The state is only updated, if `result` succeeds.
+    fn try_feed_with<T, R, F>(
+        &mut self,
+        buffer: &mut [T],
+        value: T,
+        result: F,
+    ) -> Result<R, MovAvgError>
+    where
+        T: Num + NumCast + Copy,
+        A: MovAvgAccu<T>,
+        F: FnOnce(&Self, &[T]) -> Result<R, MovAvgError>,
+    {
+        let size = buffer.len();
+        debug_assert!(self.nr_items <= size);
+
+        // Get the first element from the moving window state.
+        let first_value = if self.nr_items >= size {
+            A::from(buffer[self.index]).ok_or(MovAvgError::CastInput)?
+        } else {
+            A::zero()
+        };
+
+        let a_value = A::from(value).ok_or(MovAvgError::CastInput)?;
+
+        // Calculate the new moving window state fill state.
+        let new_nr_items = if self.nr_items >= size {
+            self.nr_items // Already fully populated.
+        } else {
+            self.nr_items + 1
+        };
+
+        // Insert the new value into the moving window state.
+        // If en error happens later, orig_item has to be restored.
+        let orig_item = buffer[self.index];
+        buffer[self.index] = value;
+
+        // Recalculate the accumulator.
+        let window_buffer = &buffer[0..new_nr_items];
+        let policy = self.policy;
+        let new_accu = match self.mode {
+            AccuMode::Exact => self
+                .accu
+                .recalc_accu_exact(first_value, a_value, window_buffer, policy)
+                .map(|accu| (accu, A::zero())),
+            AccuMode::Incremental => self
+                .accu
+                .recalc_accu_with(first_value, a_value, window_buffer, policy)
+                .map(|accu| (accu, A::zero())),
+            AccuMode::Resync(interval) => if self.nr_since_resync + 1 >= interval {
+                self.accu
+                    .recalc_accu_exact(first_value, a_value, window_buffer, policy)
+            } else {
+                self.accu
+                    .recalc_accu_with(first_value, a_value, window_buffer, policy)
+            }
+            .map(|accu| (accu, A::zero())),
+            AccuMode::Compensated => self.accu.recalc_accu_compensated(
+                self.comp,
+                first_value,
+                a_value,
+                window_buffer,
+                policy,
+            ),
+        };
+        let new_state = new_accu.map(|(accu, comp)| {
+            let saturated = if policy == OverflowPolicy::Saturating && !A::is_float() {
+                if self.saturated {
+                    // The exact sum is unknown. Recalculate it from the window buffer.
+                    Self::check_saturated(window_buffer, policy)
+                } else {
+                    self.accu
+                        .recalc_accu_with(
+                            first_value,
+                            a_value,
+                            window_buffer,
+                            OverflowPolicy::Checked,
+                        )
+                        .is_err()
+                }
+            } else {
+                false
+            };
+            let nr_since_resync = match self.mode {
+                AccuMode::Resync(interval) => (self.nr_since_resync + 1) % interval.max(1),
+                _ => self.nr_since_resync,
+            };
+            SmaState {
+                accu,
+                comp,
+                saturated,
+                nr_since_resync,
+                nr_items: new_nr_items,
+                index: (self.index + 1) % size,
+                ..*self
+            }
+        });
+
+        // Calculate the return value.
+        match new_state.and_then(|state| Ok((result(&state, window_buffer)?, state))) {
+            Ok((ret, state)) => {
+                // Update the state.
+                *self = state;
+
+                // Return the end result.
+                Ok(ret)
+            }
+            Err(e) => {
+                // Restore the original moving window state.
+                buffer[self.index] = orig_item;
+                Err(e)
+            }
+        }
+    }
+
+    /// Get the current average.
+    pub(crate) fn try_get<T>(&self, buffer: &[T]) -> Result<T, MovAvgError>
+    where
+        T: Num + NumCast + Copy,
+        A: MovAvgAccu<T>,
+    {
+        if let Some(nr_items) = A::from(self.nr_items) {
+            if nr_items == A::zero() {
+                Err(MovAvgError::Empty)
+            } else if self.saturated {
+                let (avg, _, _) = A::saturating_avg(&buffer[..self.nr_items], self.rounding)?;
+                T::from(avg).ok_or(MovAvgError::CastResult)
+            } else {
+                let avg = (self.accu + self.comp).div_accu(nr_items, self.rounding);
+                // The division of float accumulators is not rounded.
+                let avg = if is_fractional::<T>() {
+                    avg
+                } else {
+                    avg.round_accu(self.rounding)
+                };
+                T::from(avg).ok_or(MovAvgError::CastResult)
+            }
+        } else {
+            Err(MovAvgError::CastCount)
+        }
+    }
+
+    /// Get the current sum of all items in the window.
+    ///
+    /// A saturated sum is clamped to the limits of the accumulator type.
+    #[inline]
+    pub(crate) fn sum<T>(&self, buffer: &[T]) -> A
+    where
+        T: Num + NumCast + Copy,
+        A: MovAvgAccu<T>,
+    {
+        if self.saturated {
+            if let Ok((_, sum, _)) = A::saturating_avg(&buffer[..self.nr_items], self.rounding) {
+                return sum;
+            }
+        }
+        self.accu + self.comp
+    }
+
+    /// Get the current average as an exact fraction `(sum, nr_items)`.
+    pub(crate) fn try_get_fraction(&self) -> Result<(A, A), MovAvgError> {
+        if self.nr_items == 0 {
+            Err(MovAvgError::Empty)
+        } else if self.saturated {
+            Err(MovAvgError::AccuOverflow)
+        } else {
+            let nr_items = A::from(self.nr_items).ok_or(MovAvgError::CastCount)?;
+            Ok((self.accu + self.comp, nr_items))
+        }
+    }
+
+    /// Get the current average converted to the output type `O`.
+    ///
+    /// If `O` can represent fractions (e.g. floats), then the division
+    /// is done in the output type `O`.
+    /// Otherwise the division is done in the accumulator type `A` with rounding.
+    pub(crate) fn try_get_as<T, O>(&self, buffer: &[T]) -> Result<O, MovAvgError>
+    where
+        T: Num + NumCast + Copy,
+        A: MovAvgAccu<T>,
+        O: Num + NumCast + Copy + MovAvgAccu<T>,
+    {
+        if self.nr_items == 0 {
+            return Err(MovAvgError::Empty);
+        }
+        let window_buffer = &buffer[..self.nr_items];
+        if self.saturated && !O::is_float() {
+            let (avg, _, _) = A::saturating_avg(window_buffer, self.rounding)?;
+            return O::from(avg).ok_or(MovAvgError::CastResult);
+        }
+        if O::is_float() {
+            let o_sum = if self.saturated {
+                // The sum does not fit into the accumulator. Sum up in the output type.
+                let mut o_sum = O::zero();
+                for value in window_buffer {
+                    o_sum = o_sum + O::from(*value).ok_or(MovAvgError::CastResult)?;
+                }
+                o_sum
+            } else {
+                O::from(self.accu + self.comp).ok_or(MovAvgError::CastResult)?
+            };
+            let o_nr_items = O::from(self.nr_items).ok_or(MovAvgError::CastCount)?;
+            Ok(o_sum / o_nr_items)
+        } else {
+            let nr_items = A::from(self.nr_items).ok_or(MovAvgError::CastCount)?;
+            // The division of float accumulators is not rounded.
+            let avg = (self.accu + self.comp)
+                .div_accu(nr_items, self.rounding)
+                .round_accu(self.rounding);
+            O::from(avg).ok_or(MovAvgError::CastResult)
+        }
+    }
+}
+
+/// Iterator over the populated items of a Moving Average window.
+///
+/// The items are yielded in chronological order from the oldest to the newest item.
+///
+/// See [MovAvg::iter].
+#[derive(Clone, Debug)]
+pub struct WindowIter<'a, T> {
+    inner: core::iter::Chain<core::slice::Iter<'a, T>, core::slice::Iter<'a, T>>,
+}
+
+impl<'a, T: Copy> WindowIter<'a, T> {
+    /// Construct an iterator over the two ring halves `(older, newer)`.
+    pub(crate) fn new(slices: (&'a [T], &'a [T])) -> Self {
+        let (older, newer) = slices;
+        WindowIter {
+            inner: older.iter().chain(newer.iter()),
+        }
+    }
+}
+
+impl<T: Copy> Iterator for WindowIter<'_, T> {
+    type Item = T;
+
+    #[inline]
+    fn next(&mut self) -> Option<T> {
+        self.inner.next().copied()
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+impl<T: Copy> DoubleEndedIterator for WindowIter<'_, T> {
+    #[inline]
+    fn next_back(&mut self) -> Option<T> {
+        self.inner.next_back().copied()
+    }
+}
+
+impl<T: Copy> ExactSizeIterator for WindowIter<'_, T> {}
+
+impl<T: Copy> core::iter::FusedIterator for WindowIter<'_, T> {}
+
+// Generate the methods that are shared by all Simple Moving Average types.
+// The type must have a `buffer` field holding the window items
+// and a `state` field holding the SmaState.
+// `name` is the type name used in the documentation
+// and the other arguments are the per type examples of the methods.
+macro_rules! impl_sma_methods {
+    (
+        name: $name:literal,
+        with_accu_mode: { $(#[$with_accu_mode:meta])* },
+        with_rounding: { $(#[$with_rounding:meta])* },
+        with_overflow_policy: { $(#[$with_overflow_policy:meta])* },
+        try_feed_slice: { $(#[$try_feed_slice:meta])* },
+        try_get_as: { $(#[$try_get_as:meta])* },
+        sum: { $(#[$sum:meta])* },
+        iter: { $(#[$iter:meta])* },
+        as_slices: { $(#[$as_slices:meta])* },
+    ) => {
+            /// Set the accumulation strategy of this instance.
+            ///
+            /// The accumulator is recalculated from the already populated window items.
+            ///
+            /// See [AccuMode] for the available strategies.
+            ///
+            /// # Panics
+            ///
+            /// Panics, if the accumulator recalculation fails. (e.g. due to overflow).
+            $(#[$with_accu_mode])*
+            pub fn with_accu_mode(mut self, mode: AccuMode) -> Self {
+                self.state
+                    .set_accu_mode(&self.buffer, mode)
+                    .expect("Failed to initialize the MovAvg.");
+                self
+            }
+
+            /// Get the accumulation strategy of this instance.
+            #[inline]
+            pub const fn accu_mode(&self) -> AccuMode {
+                self.state.accu_mode()
+            }
+
+            /// Set the rounding mode of integer average results of this instance.
+            ///
+            /// The rounding mode is applied to the results of `feed` and `get`.
+            ///
+            /// See [Rounding] for the available rounding modes.
+            $(#[$with_rounding])*
+            pub fn with_rounding(mut self, rounding: Rounding) -> Self {
+                self.state.set_rounding(rounding);
+                self
+            }
+
+            /// Get the rounding mode of integer average results of this instance.
+            #[inline]
+            pub const fn rounding(&self) -> Rounding {
+                self.state.rounding()
+            }
+
+            /// Set the overflow policy of the integer accumulator of this instance.
+            ///
+            /// The accumulator is recalculated from the already populated window items.
+            ///
+            /// See [OverflowPolicy] for the available policies.
+            ///
+            /// # Panics
+            ///
+            /// Panics, if the accumulator recalculation fails. (e.g. due to overflow).
+            $(#[$with_overflow_policy])*
+            pub fn with_overflow_policy(mut self, policy: OverflowPolicy) -> Self {
+                self.state
+                    .set_overflow_policy(&self.buffer, policy)
+                    .expect("Failed to initialize the MovAvg.");
+                self
+            }
+
+            /// Get the overflow policy of the integer accumulator of this instance.
+            #[inline]
+            pub const fn overflow_policy(&self) -> OverflowPolicy {
+                self.state.overflow_policy()
+            }
+
+            /// Reset the Moving Average.
+            ///
+            /// This resets the accumulator and the number of accumulated items to 0,
+            #[doc = concat!(" as if this instance was re-created with [", $name, "::new].")]
+            ///
+            /// # Note
+            ///
+            /// This does not actually overwrite the buffered items in the internal buffer.
+            pub fn reset(&mut self) {
+                self.state.reset();
+            }
+
+            /// Check if no items have been fed into the Moving Average, yet.
+            ///
+            /// This returns true, if [Self::len] `==0`.
+            ///
+            /// This returns false, if [Self::len] `!=0`.
+            #[inline]
+            pub const fn is_empty(&self) -> bool {
+                self.state.len() == 0
+            }
+
+            /// Try to feed a new value into the Moving Average and return the new average.
+            ///
+            /// * `value` - The new value to feed into the Moving Average.
+            ///
+            /// On success, returns `Ok(T)` with the new Moving Average result.
+            ///
+            /// Returns `Err`, if the internal accumulator overflows, or if any value conversion fails.
+            /// Value conversion does not fail, if the types are big enough to hold the values.
+            pub fn try_feed(&mut self, value: T) -> Result<T, MovAvgError> {
+                self.state.try_feed(&mut self.buffer, value)
+            }
+
+            /// Feed a new value into the Moving Average and return the new average.
+            ///
+            /// * `value` - The new value to feed into the Moving Average.
+            ///
+            /// Returns the new Moving Average result.
+            ///
+            /// # Panics
+            ///
+            /// Panics, if the internal accumulator overflows, or if any value conversion fails.
+            /// Value conversion does not fail, if the types are big enough to hold the values.
+            pub fn feed(&mut self, value: T) -> T {
+                match self.try_feed(value) {
+                    Ok(avg) => avg,
+                    Err(e) => panic!("MovAvg calculation failed: {}", e),
+                }
+            }
+
+            /// Try to feed all values from a slice into the Moving Average and return the final average.
+            ///
+            /// * `values` - The new values to feed into the Moving Average.
+            ///
+            /// This is a convenience wrapper around [Self::try_feed].
+            /// Each value costs the same as a single [Self::try_feed],
+            /// except that the intermediate averages are not calculated.
+            ///
+            /// On success, returns `Ok(T)` with the new Moving Average result.
+            ///
+            /// Returns `Err`, if the internal accumulator overflows, or if any value conversion fails.
+            /// In this case the values before the failing value have been fed into the Moving Average.
+            ///
+            #[doc = concat!(" Returns `Err`, if `values` is empty and no values have been fed into ", $name, " before.")]
+            $(#[$try_feed_slice])*
+            pub fn try_feed_slice(&mut self, values: &[T]) -> Result<T, MovAvgError> {
+                self.try_extend(values.iter().copied())
+            }
+
+            /// Feed all values from a slice into the Moving Average and return the final average.
+            ///
+            /// See [Self::try_feed_slice] for details.
+            ///
+            /// # Panics
+            ///
+            /// Panics, if the internal accumulator overflows, or if any value conversion fails.
+            ///
+            #[doc = concat!(" Panics, if `values` is empty and no values have been fed into ", $name, " before.")]
+            pub fn feed_slice(&mut self, values: &[T]) -> T {
+                match self.try_feed_slice(values) {
+                    Ok(avg) => avg,
+                    Err(e) => panic!("MovAvg calculation failed: {}", e),
+                }
+            }
+
+            /// Try to feed all values from an iterator into the Moving Average and return the final average.
+            ///
+            /// * `values` - The new values to feed into the Moving Average.
+            ///
+            /// See [Self::try_feed_slice] for details.
+            pub fn try_extend<I>(&mut self, values: I) -> Result<T, MovAvgError>
+            where
+                I: IntoIterator<Item = T>,
+            {
+                self.state.try_feed_iter(&mut self.buffer, values)
+            }
+
+            /// Feed all values from an iterator into the Moving Average and return the final average.
+            ///
+            /// See [Self::try_feed_slice] for details.
+            ///
+            /// # Panics
+            ///
+            /// Panics, if the internal accumulator overflows, or if any value conversion fails.
+            ///
+            #[doc = concat!(" Panics, if `values` is empty and no values have been fed into ", $name, " before.")]
+            pub fn extend<I>(&mut self, values: I) -> T
+            where
+                I: IntoIterator<Item = T>,
+            {
+                match self.try_extend(values) {
+                    Ok(avg) => avg,
+                    Err(e) => panic!("MovAvg calculation failed: {}", e),
+                }
+            }
+
+            /// Try to feed all values from a slice into the Moving Average
+            /// and write all intermediate averages to the `out` slice.
+            ///
+            /// * `values` - The new values to feed into the Moving Average.
+            /// * `out` - The Moving Average result after feeding the corresponding value from `values`.
+            ///
+            /// This is a convenience wrapper, that calls [Self::try_feed] for each value.
+            ///
+            /// Returns `Err`, if the internal accumulator overflows, or if any value conversion fails.
+            /// In this case the values before the failing value have been fed into the Moving Average
+            /// and their results have been written to `out`.
+            ///
+            /// Returns `Err`, if `out` is shorter than `values`.
+            /// ([MovAvgError::BufferTooSmall])
+            /// In this case no values are fed into the Moving Average.
+            pub fn try_feed_slice_into(&mut self, values: &[T], out: &mut [T]) -> Result<(), MovAvgError> {
+                self.state
+                    .try_feed_slice_into(&mut self.buffer, values, out)
+            }
+
+            /// Feed all values from a slice into the Moving Average
+            /// and write all intermediate averages to the `out` slice.
+            ///
+            /// See [Self::try_feed_slice_into] for details.
+            ///
+            /// # Panics
+            ///
+            /// Panics, if `out` is shorter than `values`.
+            ///
+            /// Panics, if the internal accumulator overflows, or if any value conversion fails.
+            pub fn feed_slice_into(&mut self, values: &[T], out: &mut [T]) {
+                if let Err(e) = self.try_feed_slice_into(values, out) {
+                    panic!("MovAvg calculation failed: {}", e);
+                }
+            }
+
+            /// Try to get the current Moving Average value.
+            /// This method does not modify the internal state.
+            ///
+            /// Returns `Err`, if the internal state is empty.
+            #[doc = concat!(" That is if no values have been fed into ", $name, ".")]
+            ///
+            /// Returns `Err`, if any value conversion fails.
+            /// Value conversion does not fail, if the types are big enough to hold the values.
+            pub fn try_get(&self) -> Result<T, MovAvgError> {
+                self.state.try_get(&self.buffer)
+            }
+
+            /// Get the current Moving Average value.
+            /// This method does not modify the internal state.
+            ///
+            /// # Panics
+            ///
+            /// Panics, if the internal state is empty.
+            #[doc = concat!(" That is if no values have been fed into ", $name, ".")]
+            ///
+            /// Panics, if any value conversion fails.
+            /// Value conversion does not fail, if the types are big enough to hold the values.
+            pub fn get(&self) -> T {
+                match self.try_get() {
+                    Ok(avg) => avg,
+                    Err(e) => panic!("MovAvg calculation failed: {}", e),
+                }
+            }
+
+            /// Try to get the current Moving Average value converted to the output type `O`.
+            /// This method does not modify the internal state.
+            ///
+            /// The result is calculated from the internal accumulator
+            /// and it is not converted to the input type `T` first.
+            /// If `O` can represent fractions (e.g. `f32` or `f64`), then the division
+            /// by the number of items is done in `O`.
+            /// Otherwise the division is done in the accumulator type
+            /// and the rounding mode ([Self::with_rounding]) is applied.
+            ///
+            /// Returns `Err`, if the internal state is empty.
+            #[doc = concat!(" That is if no values have been fed into ", $name, ".")]
+            ///
+            /// Returns `Err`, if any value conversion fails.
+            $(#[$try_get_as])*
+            pub fn try_get_as<O>(&self) -> Result<O, MovAvgError>
+            where
+                O: Num + NumCast + Copy + MovAvgAccu<T>,
+            {
+                self.state.try_get_as::<T, O>(&self.buffer)
+            }
+
+            /// Get the current Moving Average value converted to the output type `O`.
+            /// This method does not modify the internal state.
+            ///
+            /// See [Self::try_get_as] for details.
+            ///
+            /// # Panics
+            ///
+            /// Panics, if the internal state is empty.
+            #[doc = concat!(" That is if no values have been fed into ", $name, ".")]
+            ///
+            /// Panics, if any value conversion fails.
+            pub fn get_as<O>(&self) -> O
+            where
+                O: Num + NumCast + Copy + MovAvgAccu<T>,
+            {
+                match self.try_get_as() {
+                    Ok(avg) => avg,
+                    Err(e) => panic!("MovAvg calculation failed: {}", e),
+                }
+            }
+
+            /// Get the current sum of all items in the Moving Average window.
+            /// This method does not modify the internal state.
+            ///
+            /// This is the raw internal accumulator value.
+            /// Together with [Self::len] it represents the exact Moving Average value.
+            ///
+            /// With [OverflowPolicy::Saturating] the sum is clamped to the limits
+            /// of the accumulator type, if it does not fit into the accumulator.
+            ///
+            /// The sum is zero, if the internal state is empty.
+            $(#[$sum])*
+            #[inline]
+            pub fn sum(&self) -> A {
+                self.state.sum(&self.buffer)
+            }
+
+            /// Try to get the current Moving Average value as exact fraction.
+            /// This method does not modify the internal state.
+            ///
+            /// On success, returns `Ok((numerator, denominator))`.
+            /// The numerator is the sum of all items in the window ([Self::sum])
+            /// and the denominator is the number of items in the window ([Self::len]).
+            ///
+            /// Returns `Err`, if the internal state is empty.
+            #[doc = concat!(" That is if no values have been fed into ", $name, ".")]
+            ///
+            /// Returns `Err`, if the number of items can't be converted to the accumulator type.
+            ///
+            /// Returns `Err`, if the sum does not fit into the accumulator.
+            /// (See [OverflowPolicy::Saturating]).
+            pub fn try_get_fraction(&self) -> Result<(A, A), MovAvgError> {
+                self.state.try_get_fraction()
+            }
+
+            /// Get the current Moving Average value as exact fraction.
+            /// This method does not modify the internal state.
+            ///
+            /// See [Self::try_get_fraction] for details.
+            ///
+            /// # Panics
+            ///
+            /// Panics, if the internal state is empty.
+            #[doc = concat!(" That is if no values have been fed into ", $name, ".")]
+            ///
+            /// Panics, if the number of items can't be converted to the accumulator type.
+            pub fn get_fraction(&self) -> (A, A) {
+                match self.try_get_fraction() {
+                    Ok(fraction) => fraction,
+                    Err(e) => panic!("MovAvg calculation failed: {}", e),
+                }
+            }
+
+            /// Get an iterator over the items in the window.
+            ///
+            /// The items are yielded in chronological order from the oldest to the newest item.
+            /// Only the populated items are yielded. That are [Self::len] items.
+            $(#[$iter])*
+            pub fn iter(&self) -> WindowIter<'_, T> {
+                WindowIter::new(self.as_slices())
+            }
+
+            /// Get the oldest item in the window.
+            ///
+            /// That is the item that will be evicted next, if the window is fully populated.
+            ///
+            /// Returns `None`, if the window is empty.
+            pub fn oldest(&self) -> Option<T> {
+                self.iter().next()
+            }
 
-#[cfg(has_i128)]
-impl_int_accu!(i128, u128);
+            /// Get the newest item in the window.
+            ///
+            /// That is the most recently fed item.
+            ///
+            /// Returns `None`, if the window is empty.
+            pub fn newest(&self) -> Option<T> {
+                self.iter().next_back()
+            }
 
-impl_float_accu!(f32, f64);
+            /// Get the items in the window as two contiguous slices of the ring buffer.
+            ///
+            /// The first slice holds the older items and the second slice holds the newer items.
+            /// Both slices concatenated are all populated items in chronological order
+            /// from the oldest to the newest item.
+            /// The second slice is empty, if the items are contiguous in the ring buffer.
+            $(#[$as_slices])*
+            pub fn as_slices(&self) -> (&[T], &[T]) {
+                self.state.as_slices(&self.buffer)
+            }
+    };
+}
+pub(crate) use impl_sma_methods;
 
 /// Simple Moving Average (SMA)
 ///
@@ -124,15 +1674,13 @@ impl_float_accu!(f32, f64);
 ///
 /// * `T` - The type of the `feed()` input value.
 /// * `A` - The type of the internal accumulator.
-///         This type must be bigger then or equal to `T`.
+///   This type must be bigger then or equal to `T`.
 /// * `WINDOW_SIZE` - The size of the sliding window.
-///                   In number of fed elements.
+///   In number of fed elements.
 #[derive(Clone, Debug)]
 pub struct MovAvg<T, A, const WINDOW_SIZE: usize> {
-    buffer: [T; WINDOW_SIZE],
-    accu: A,
-    nr_items: usize,
-    index: usize,
+    pub(crate) buffer: [T; WINDOW_SIZE],
+    pub(crate) state: SmaState<A>,
 }
 
 impl<T, A, const WINDOW_SIZE: usize> MovAvg<T, A, WINDOW_SIZE>
@@ -140,6 +1688,10 @@ where
     T: Num + NumCast + Copy,
     A: Num + NumCast + Copy + MovAvgAccu<T>,
 {
+    /// Compile time check of the `WINDOW_SIZE` generic.
+    pub(crate) const WINDOW_SIZE_CHECK: () =
+        assert!(WINDOW_SIZE > 0, "WINDOW_SIZE must not be zero.");
+
     /// Construct a new Simple Moving Average.
     ///
     /// The internal accumulator defaults to zero.
@@ -152,20 +1704,44 @@ where
     /// let mut avg: MovAvg<i32, i32, 3> = MovAvg::new(); // window size = 3
     /// assert_eq!(avg.feed(10), 10);
     /// ```
+    ///
+    /// A `WINDOW_SIZE` of zero is rejected at compile time:
+    ///
+    /// ```compile_fail
+    /// use movavg::MovAvg;
+    ///
+    /// let mut avg: MovAvg<i32, i32, 0> = MovAvg::new();
+    /// ```
     pub fn new() -> MovAvg<T, A, WINDOW_SIZE> {
-        assert!(WINDOW_SIZE > 0);
         Self::new_init([T::one(); WINDOW_SIZE], 0)
     }
 
+    /// Try to construct a new Simple Moving Average.
+    ///
+    /// This is the same as [MovAvg::new], but it returns a `Result`
+    /// instead of panicking.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use movavg::MovAvg;
+    ///
+    /// let mut avg: MovAvg<i32, i32, 3> = MovAvg::try_new().unwrap();
+    /// assert_eq!(avg.feed(10), 10);
+    /// ```
+    pub fn try_new() -> Result<MovAvg<T, A, WINDOW_SIZE>, MovAvgError> {
+        Self::try_new_init([T::one(); WINDOW_SIZE], 0)
+    }
+
     /// Construct a new Simple Moving Average from a pre-allocated buffer
     /// and initialize its internal state.
     ///
     /// * `buffer` - (Partially) pre-populated window buffer. Contains the window values.
-    ///              The length of this buffer defines the Moving Average window size.
+    ///   The length of this buffer defines the Moving Average window size.
     /// * `nr_populated` - The number of pre-populated Moving Average window elements in `buffer`.
-    ///                    `nr_populated` must be less than or equal to `buffer.len()`.
-    ///                    The populated values in `buffer` must begin at index 0.
-    ///                    The values of unpopulated elements in `buffer` does not matter.
+    ///   `nr_populated` must be less than or equal to `buffer.len()`.
+    ///   The populated values in `buffer` must begin at index 0.
+    ///   The values of unpopulated elements in `buffer` does not matter.
     ///
     /// # Panics
     ///
@@ -191,38 +1767,43 @@ where
     /// assert_eq!(avg.feed(60), 40);
     /// ```
     pub fn new_init(buffer: [T; WINDOW_SIZE], nr_populated: usize) -> MovAvg<T, A, WINDOW_SIZE> {
-        let size = buffer.len();
-        assert!(WINDOW_SIZE > 0);
-        assert!(size == WINDOW_SIZE);
-
-        let nr_items = nr_populated;
-        assert!(nr_items <= size);
-
-        let index = nr_items % size;
-
-        let accu =
-            initialize_accu(&buffer[0..nr_items]).expect("Failed to initialize the accumulator.");
-
-        MovAvg {
-            buffer,
-            accu,
-            nr_items,
-            index,
-        }
+        Self::try_new_init(buffer, nr_populated).expect("Failed to initialize the MovAvg.")
     }
 
-    /// Reset the Moving Average.
+    /// Try to construct a new Simple Moving Average from a pre-allocated buffer
+    /// and initialize its internal state.
     ///
-    /// This resets the accumulator and the number of accumulated items to 0,
-    /// as if this instance was re-created with [MovAvg::new].
+    /// This is the same as [MovAvg::new_init], but it returns a `Result`
+    /// instead of panicking.
     ///
-    /// # Note
+    /// Returns `Err`, if:
+    /// * `nr_populated` is bigger than `buffer.len()`.
+    ///   ([MovAvgError::InvalidNrPopulated])
+    /// * The initial accumulator calculation fails. (e.g. due to overflow).
     ///
-    /// This does not actually overwrite the buffered items in the internal buffer.
-    pub fn reset(&mut self) {
-        self.accu = A::zero();
-        self.nr_items = 0;
-        self.index = 0;
+    /// # Examples
+    ///
+    /// ```
+    /// use movavg::{MovAvg, MovAvgError};
+    ///
+    /// let avg: Result<MovAvg<i32, i32, 3>, _> = MovAvg::try_new_init([10, 20, 30], 4);
+    /// assert_eq!(avg.unwrap_err(), MovAvgError::InvalidNrPopulated);
+    ///
+    /// let avg: Result<MovAvg<u8, u8, 3>, _> = MovAvg::try_new_init([100, 200, 0], 2);
+    /// assert_eq!(avg.unwrap_err(), MovAvgError::AccuOverflow);
+    ///
+    /// let avg: MovAvg<u8, u16, 3> = MovAvg::try_new_init([100, 200, 0], 2).unwrap();
+    /// assert_eq!(avg.get(), 150);
+    /// ```
+    pub fn try_new_init(
+        buffer: [T; WINDOW_SIZE],
+        nr_populated: usize,
+    ) -> Result<MovAvg<T, A, WINDOW_SIZE>, MovAvgError> {
+        let () = Self::WINDOW_SIZE_CHECK;
+
+        let state = SmaState::try_new_init(&buffer, nr_populated)?;
+
+        Ok(MovAvg { buffer, state })
     }
 
     /// Get the current length of the Moving Average window.
@@ -234,17 +1815,7 @@ where
     /// See [Self::window_size] to get the nominal maximum length of the window.
     #[inline]
     pub const fn len(&self) -> usize {
-        self.nr_items
-    }
-
-    /// Check if no items have been fed into the Moving Average, yet.
-    ///
-    /// This returns true, if [Self::len] `==0`.
-    ///
-    /// This returns false, if [Self::len] `!=0`.
-    #[inline]
-    pub const fn is_empty(&self) -> bool {
-        self.nr_items == 0
+        self.state.len()
     }
 
     /// Get the nominal size of the Moving Average window.
@@ -257,120 +1828,175 @@ where
         WINDOW_SIZE
     }
 
-    /// Try to feed a new value into the Moving Average and return the new average.
-    ///
-    /// * `value` - The new value to feed into the Moving Average.
-    ///
-    /// On success, returns `Ok(T)` with the new Moving Average result.
-    ///
-    /// Returns `Err`, if the internal accumulator overflows, or if any value conversion fails.
-    /// Value conversion does not fail, if the types are big enough to hold the values.
-    pub fn try_feed(&mut self, value: T) -> Result<T, &str> {
-        let size = self.buffer.len();
-        debug_assert!(self.nr_items <= size);
-
-        // Get the first element from the moving window state.
-        let first_value = if self.nr_items >= size {
-            A::from(self.buffer[self.index])
-                .ok_or("Failed to cast first value to accumulator type.")?
-        } else {
-            A::zero()
-        };
-
-        let a_value = A::from(value).ok_or("Failed to cast value to accumulator type.")?;
-
-        // Calculate the new moving window state fill state.
-        let new_nr_items = if self.nr_items >= size {
-            self.nr_items // Already fully populated.
-        } else {
-            self.nr_items + 1
-        };
-        let a_nr_items =
-            A::from(new_nr_items).ok_or("Failed to cast number-of-items to accumulator type.")?;
-
-        // Insert the new value into the moving window state.
-        // If en error happens later, orig_item has to be restored.
-        let orig_item = self.buffer[self.index];
-        self.buffer[self.index] = value;
-
-        // Recalculate the accumulator.
-        match self
-            .accu
-            .recalc_accu(first_value, a_value, &self.buffer[0..new_nr_items])
-        {
-            Ok(new_accu) => {
-                // Calculate the new average.
-                match T::from(new_accu / a_nr_items) {
-                    Some(avg) => {
-                        // Update the state.
-                        self.nr_items = new_nr_items;
-                        self.index = (self.index + 1) % size;
-                        self.accu = new_accu;
-
-                        // Return the end result.
-                        Ok(avg)
-                    }
-                    None => {
-                        // Restore the original moving window state.
-                        self.buffer[self.index] = orig_item;
-                        Err("Failed to cast result to item type.")
-                    }
-                }
-            }
-            Err(e) => {
-                // Restore the original moving window state.
-                self.buffer[self.index] = orig_item;
-                Err(e)
-            }
-        }
+    impl_sma_methods! {
+        name: "MovAvg",
+        with_accu_mode: {
+            ///
+            /// # Examples
+            ///
+            /// ```
+            /// use movavg::{AccuMode, MovAvg};
+            ///
+            /// let mut avg: MovAvg<f64, f64, 3> = MovAvg::new().with_accu_mode(AccuMode::Compensated);
+            /// avg.feed(1e16);
+            /// avg.feed(1.0);
+            /// avg.feed(1.0);
+            /// assert_eq!(avg.feed(3.0), (1.0 + 1.0 + 3.0) / 3.0);
+            /// ```
+        },
+        with_rounding: {
+            ///
+            /// # Examples
+            ///
+            /// ```
+            /// use movavg::{MovAvg, Rounding};
+            ///
+            /// let mut avg: MovAvg<i32, i32, 2> = MovAvg::new().with_rounding(Rounding::HalfEven);
+            /// avg.feed(10);
+            /// assert_eq!(avg.feed(15), 12); // 12.5
+            /// assert_eq!(avg.feed(18), 16); // 16.5
+            /// assert_eq!(avg.feed(-20), -1); // -1.0
+            /// assert_eq!(avg.feed(-19), -20); // -19.5
+            /// ```
+        },
+        with_overflow_policy: {
+            ///
+            /// # Examples
+            ///
+            /// ```
+            /// use movavg::{MovAvg, OverflowPolicy};
+            ///
+            /// let mut avg: MovAvg<u8, u8, 2> = MovAvg::new().with_overflow_policy(OverflowPolicy::Wrapping);
+            /// avg.feed(200);
+            /// avg.feed(100); // The sum wraps around.
+            /// assert_eq!(avg.feed(20), 60); // The sum of the window fits again.
+            ///
+            /// let mut avg: MovAvg<u8, u8, 2> = MovAvg::new().with_overflow_policy(OverflowPolicy::Saturating);
+            /// avg.feed(200);
+            /// assert_eq!(avg.feed(100), 150); // The sum does not fit, but the average does.
+            /// assert_eq!(avg.sum(), 255);
+            /// assert_eq!(avg.feed(20), 60);
+            /// ```
+        },
+        try_feed_slice: {
+            ///
+            /// # Examples
+            ///
+            /// ```
+            /// use movavg::MovAvg;
+            ///
+            /// let mut avg: MovAvg<i32, i32, 3> = MovAvg::new();
+            /// assert_eq!(avg.feed_slice(&[10, 20, 30, 40]), 30);
+            /// assert_eq!(avg.extend([50, 60]), 50);
+            ///
+            /// let mut out = [0; 2];
+            /// avg.feed_slice_into(&[70, 80], &mut out);
+            /// assert_eq!(out, [60, 70]);
+            /// ```
+        },
+        try_get_as: {
+            ///
+            /// # Examples
+            ///
+            /// ```
+            /// use movavg::MovAvg;
+            ///
+            /// let mut avg: MovAvg<u8, u16, 4> = MovAvg::new();
+            /// avg.feed(100);
+            /// avg.feed(101);
+            /// avg.feed(101);
+            /// assert_eq!(avg.feed(101), 100);
+            /// assert_eq!(avg.get_as::<f32>(), 100.75);
+            /// assert_eq!(avg.get_as::<i64>(), 100);
+            /// ```
+        },
+        sum: {
+            ///
+            /// # Examples
+            ///
+            /// ```
+            /// use movavg::MovAvg;
+            ///
+            /// let mut avg: MovAvg<i32, i64, 3> = MovAvg::new();
+            /// avg.feed(10);
+            /// avg.feed(20);
+            /// assert_eq!(avg.feed(31), 20);
+            /// assert_eq!(avg.sum(), 61);
+            /// assert_eq!(avg.get_fraction(), (61, 3));
+            ///
+            /// // Quotient and remainder
+            /// let (num, den) = avg.get_fraction();
+            /// assert_eq!((num / den, num % den), (20, 1));
+            /// ```
+        },
+        iter: {
+            ///
+            /// # Examples
+            ///
+            /// ```
+            /// use movavg::MovAvg;
+            ///
+            /// let mut avg: MovAvg<i32, i32, 3> = MovAvg::new_init([10, 20, 0], 2);
+            /// assert!(avg.iter().eq([10, 20]));
+            /// avg.feed(30);
+            /// avg.feed(40); // 10 is evicted
+            /// assert!(avg.iter().eq([20, 30, 40]));
+            /// assert!(avg.iter().rev().eq([40, 30, 20]));
+            /// ```
+        },
+        as_slices: {
+            ///
+            /// # Examples
+            ///
+            /// ```
+            /// use movavg::MovAvg;
+            ///
+            /// let mut avg: MovAvg<i32, i32, 3> = MovAvg::new();
+            /// avg.feed(1);
+            /// avg.feed(2);
+            /// assert_eq!(avg.as_slices(), (&[1, 2][..], &[][..]));
+            /// avg.feed(3);
+            /// avg.feed(4);
+            /// assert_eq!(avg.as_slices(), (&[2, 3][..], &[4][..]));
+            /// ```
+        },
     }
+}
 
-    /// Feed a new value into the Moving Average and return the new average.
-    ///
-    /// * `value` - The new value to feed into the Moving Average.
-    ///
-    /// Returns the new Moving Average result.
-    ///
-    /// # Panics
-    ///
-    /// Panics, if the internal accumulator overflows, or if any value conversion fails.
-    /// Value conversion does not fail, if the types are big enough to hold the values.
-    pub fn feed(&mut self, value: T) -> T {
-        self.try_feed(value).expect("MovAvg calculation failed.")
-    }
+impl<T, const WINDOW_SIZE: usize> MovAvg<T, <T as WideAccu>::Accu, WINDOW_SIZE>
+where
+    T: Num + NumCast + Copy + WideAccu,
+    <T as WideAccu>::Accu: Num + NumCast + Copy + MovAvgAccu<T>,
+{
+    /// Compile time check of the accumulator capacity.
+    const CAPACITY_CHECK: () = assert!(
+        WINDOW_SIZE <= <T as WideAccu>::MAX_WINDOW_SIZE,
+        "WINDOW_SIZE * T::MAX does not fit into the accumulator."
+    );
 
-    /// Try to get the current Moving Average value.
-    /// This method does not modify the internal state.
+    /// Construct a new Simple Moving Average with the wide default accumulator type.
     ///
-    /// Returns `Err`, if the internal state is empty.
-    /// That is if no values have been fed into MovAvg.
+    /// This is the same as [MovAvg::new], but it also checks at compile time
+    /// that `WINDOW_SIZE` items of any value fit into the accumulator.
+    /// Therefore, the accumulator can never overflow.
     ///
-    /// Returns `Err`, if any value conversion fails.
-    /// Value conversion does not fail, if the types are big enough to hold the values.
-    pub fn try_get(&self) -> Result<T, &str> {
-        if let Some(nr_items) = A::from(self.nr_items) {
-            if nr_items == A::zero() {
-                Err("The MovAvg state is empty.")
-            } else {
-                T::from(self.accu / nr_items).ok_or("Failed to cast result to item type.")
-            }
-        } else {
-            Err("Failed to cast number-of-items to accumulator type.")
-        }
-    }
-
-    /// Get the current Moving Average value.
-    /// This method does not modify the internal state.
+    /// This is the only constructor with this check.
+    /// The other constructors of [WideMovAvg](crate::WideMovAvg) do not check
+    /// the accumulator capacity at compile time.
     ///
-    /// # Panics
+    /// See [WideAccu] and [WideMovAvg](crate::WideMovAvg).
+    ///
+    /// # Examples
     ///
-    /// Panics, if the internal state is empty.
-    /// That is if no values have been fed into MovAvg.
+    /// ```
+    /// use movavg::MovAvg;
     ///
-    /// Panics, if any value conversion fails.
-    /// Value conversion does not fail, if the types are big enough to hold the values.
-    pub fn get(&self) -> T {
-        self.try_get().expect("MovAvg calculation failed.")
+    /// let mut avg: MovAvg<u8, u32, 100> = MovAvg::new_wide();
+    /// assert_eq!(avg.feed(255), 255);
+    /// ```
+    pub fn new_wide() -> Self {
+        let () = Self::CAPACITY_CHECK;
+        Self::new()
     }
 }
 
@@ -632,7 +2258,7 @@ mod tests {
     fn test_accu_overflow() {
         let mut a: MovAvg<u8, u8, 3> = MovAvg::new();
         a.feed(200);
-        assert!(a.try_feed(200).is_err());
+        assert_eq!(a.try_feed(200), Err(MovAvgError::AccuOverflow));
     }
 
     #[test]
@@ -698,6 +2324,30 @@ mod tests {
         assert_eq!(a.feed(60), (20 + 50 + 60) / 3);
     }
 
+    #[test]
+    fn test_try_new_init() {
+        let a: Result<MovAvg<i32, i32, 3>, _> = MovAvg::try_new_init([10, 20, 30], 3);
+        assert_eq!(a.unwrap().get(), 20);
+
+        let a: Result<MovAvg<i32, i32, 3>, _> = MovAvg::try_new_init([10, 20, 30], 4);
+        assert_eq!(a.unwrap_err(), MovAvgError::InvalidNrPopulated);
+
+        let a: Result<MovAvg<i8, i8, 3>, _> = MovAvg::try_new_init([-100, -100, 0], 2);
+        assert_eq!(a.unwrap_err(), MovAvgError::AccuOverflow);
+
+        let a: Result<MovAvg<i32, u8, 3>, _> = MovAvg::try_new_init([1, -1, 0], 2);
+        assert_eq!(a.unwrap_err(), MovAvgError::CastInput);
+
+        let a: Result<MovAvg<i32, i32, 3>, _> = MovAvg::try_new();
+        assert!(a.unwrap().is_empty());
+    }
+
+    #[test]
+    #[should_panic(expected = "Failed to initialize the MovAvg")]
+    fn test_new_init_panic() {
+        let _a: MovAvg<i32, i32, 3> = MovAvg::new_init([10, 20, 30], 4); // this panics
+    }
+
     #[test]
     fn test_reset() {
         let mut a: MovAvg<i32, i32, 5> = MovAvg::new();
@@ -738,7 +2388,7 @@ mod tests {
     #[test]
     fn test_get_empty() {
         let a: MovAvg<i32, i32, 3> = MovAvg::new();
-        assert!(a.try_get().is_err());
+        assert_eq!(a.try_get(), Err(MovAvgError::Empty));
         assert_eq!(a.len(), 0);
     }
 
@@ -749,11 +2399,534 @@ mod tests {
         assert_eq!(a.get(), 42); // this panics
     }
 
+    #[test]
+    fn test_cast_errors() {
+        let mut a: MovAvg<i32, u8, 3> = MovAvg::new();
+        assert_eq!(a.try_feed(-1), Err(MovAvgError::CastInput));
+        assert_eq!(a.try_feed(256), Err(MovAvgError::CastInput));
+        assert!(a.is_empty());
+
+        let mut a: MovAvg<u16, u8, 300> = MovAvg::new();
+        for _ in 0..255 {
+            a.feed(0);
+        }
+        assert_eq!(a.try_feed(0), Err(MovAvgError::CastCount));
+        assert_eq!(a.len(), 255);
+    }
+
     #[test]
     fn test_initialize_accu() {
         let a: u16 = initialize_accu(&[1_u32, 10_u32, 100_u32, 0_u32, 1000_u32]).unwrap();
         assert_eq!(a, 1111);
     }
+
+    #[test]
+    fn test_neumaier_add() {
+        let (sum, comp) = neumaier_add(0.0_f64, 0.0, 1.0);
+        let (sum, comp) = neumaier_add(sum, comp, 1e100);
+        let (sum, comp) = neumaier_add(sum, comp, 1.0);
+        let (sum, comp) = neumaier_add(sum, comp, -1e100);
+        assert_eq!(sum, 0.0);
+        assert_eq!(sum + comp, 2.0);
+    }
+
+    #[test]
+    fn test_compensated() {
+        let mut comp: MovAvg<f64, f64, 16> = MovAvg::new().with_accu_mode(AccuMode::Compensated);
+        assert_eq!(comp.accu_mode(), AccuMode::Compensated);
+        let mut window = [0.0_f64; 16];
+        let mut state = 0x1234_5678_u32;
+        for i in 0..100_000 {
+            // Pseudo random numbers (xorshift) with a wide range of magnitudes.
+            state ^= state << 13;
+            state ^= state >> 17;
+            state ^= state << 5;
+            let scale = [1e-3, 1.0, 1e3, 1e9][(state % 4) as usize];
+            let value = (state as f64 / u32::MAX as f64) * scale;
+            window[i % 16] = value;
+            let c = comp.feed(value);
+            if i >= 16 {
+                let e = initialize_accu::<f64, f64>(&window).unwrap() / 16.0;
+                assert!((e - c).abs() <= e.abs() * 1e-12);
+            }
+        }
+        // Flush the big values out of the window.
+        for _ in 0..16 {
+            comp.feed(0.5);
+        }
+        assert_eq!(comp.get(), 0.5);
+    }
+
+    #[test]
+    fn test_accu_modes() {
+        let default: MovAvg<f64, f64, 2> = MovAvg::new();
+        if cfg!(feature = "fastfloat") {
+            assert_eq!(default.accu_mode(), AccuMode::Incremental);
+        } else {
+            assert_eq!(default.accu_mode(), AccuMode::Exact);
+        }
+
+        let mut exact: MovAvg<f64, f64, 2> = MovAvg::new().with_accu_mode(AccuMode::Exact);
+        let mut incr: MovAvg<f64, f64, 2> = MovAvg::new().with_accu_mode(AccuMode::Incremental);
+        let mut comp: MovAvg<f64, f64, 2> = MovAvg::new().with_accu_mode(AccuMode::Compensated);
+        for value in [1e17, 1.0, 3.0] {
+            exact.feed(value);
+            incr.feed(value);
+            comp.feed(value);
+        }
+        assert_eq!(exact.get(), 2.0);
+        assert_eq!(incr.get(), 1.5); // 1.0 has been lost in cancellation.
+        assert_eq!(comp.get(), 2.0);
+
+        // Switching the mode recalculates the accumulator.
+        let incr = incr.with_accu_mode(AccuMode::Exact);
+        assert_eq!(incr.get(), 2.0);
+    }
+
+    #[test]
+    fn test_resync() {
+        const K: usize = 100;
+        let mut exact: MovAvg<f64, f64, 64> = MovAvg::new().with_accu_mode(AccuMode::Exact);
+        let mut resync: MovAvg<f64, f64, 64> = MovAvg::new().with_accu_mode(AccuMode::Resync(K));
+        let mut incr: MovAvg<f64, f64, 64> = MovAvg::new().with_accu_mode(AccuMode::Incremental);
+        let mut max_resync_err = 0.0_f64;
+        let mut max_incr_err = 0.0_f64;
+        let mut state = 0xDEAD_BEEF_u32;
+        for i in 1..=1_000_000 {
+            // Pseudo random numbers (xorshift) with a wide range of magnitudes.
+            state ^= state << 13;
+            state ^= state >> 17;
+            state ^= state << 5;
+            let scale = [1e-6, 1.0, 1e6][(state % 3) as usize];
+            let value = (state as f64 / u32::MAX as f64) * scale;
+            let e = exact.feed(value);
+            let r = resync.feed(value);
+            let n = incr.feed(value);
+            if i % K == 0 {
+                // Resynchronized in this feed.
+                assert_eq!(r, e);
+            }
+            max_resync_err = max_resync_err.max((r - e).abs());
+            max_incr_err = max_incr_err.max((n - e).abs());
+        }
+        assert!(max_resync_err < 1e-7);
+        assert!(max_resync_err < max_incr_err);
+    }
+
+    #[test]
+    fn test_resync_interval() {
+        for interval in [0, 1] {
+            let mut a: MovAvg<f64, f64, 2> =
+                MovAvg::new().with_accu_mode(AccuMode::Resync(interval));
+            for value in [1e17, 1.0, 3.0] {
+                a.feed(value);
+            }
+            assert_eq!(a.get(), 2.0);
+        }
+        let mut a: MovAvg<f64, f64, 2> = MovAvg::new().with_accu_mode(AccuMode::Resync(4));
+        assert_eq!(a.accu_mode(), AccuMode::Resync(4));
+        for value in [1e17, 1.0, 3.0] {
+            a.feed(value);
+        }
+        assert_eq!(a.get(), 1.5); // 1.0 has been lost in cancellation.
+        assert_eq!(a.feed(5.0), 4.0); // Resynchronized.
+        a.reset();
+        for value in [1e17, 1.0, 3.0] {
+            a.feed(value);
+        }
+        assert_eq!(a.get(), 1.5);
+    }
+
+    #[test]
+    fn test_int_div_round() {
+        for dividend in -50_i32..=50 {
+            for divisor in 1_i32..=7 {
+                let exact = dividend as f64 / divisor as f64;
+                let fract = exact - exact.floor();
+                let half_up = if fract >= 0.5 {
+                    exact.ceil()
+                } else {
+                    exact.floor()
+                };
+                let half_even = if fract == 0.5 {
+                    if exact.floor() % 2.0 == 0.0 {
+                        exact.floor()
+                    } else {
+                        exact.ceil()
+                    }
+                } else {
+                    exact.round()
+                };
+                let check = |rounding, expected: f64| {
+                    assert_eq!(
+                        int_div_round(dividend, divisor, rounding),
+                        expected as i32,
+                        "{} / {} {:?}",
+                        dividend,
+                        divisor,
+                        rounding
+                    );
+                };
+                check(Rounding::Truncate, exact.trunc());
+                check(Rounding::Floor, exact.floor());
+                check(Rounding::Ceil, exact.ceil());
+                check(Rounding::HalfUp, half_up);
+                check(Rounding::HalfEven, half_even);
+            }
+        }
+        assert_eq!(int_div_round(u8::MAX, 2, Rounding::HalfUp), 128);
+        assert_eq!(int_div_round(u8::MAX, 2, Rounding::HalfEven), 128);
+        assert_eq!(int_div_round(253_u8, 2, Rounding::HalfEven), 126);
+        assert_eq!(int_div_round(i8::MIN, 3, Rounding::Floor), -43);
+        assert_eq!(int_div_round(i8::MAX, 2, Rounding::Ceil), 64);
+    }
+
+    #[test]
+    fn test_rounding() {
+        let mut a: MovAvg<i32, i32, 3> = MovAvg::new();
+        assert_eq!(a.rounding(), Rounding::Truncate);
+        a.feed(1);
+        assert_eq!(a.feed(2), 1);
+        let mut a = a.with_rounding(Rounding::Ceil);
+        assert_eq!(a.rounding(), Rounding::Ceil);
+        assert_eq!(a.get(), 2);
+        assert_eq!(a.feed(-10), -2); // -7 / 3
+        assert_eq!(a.get(), -2);
+        let mut a = a.with_rounding(Rounding::Floor);
+        assert_eq!(a.get(), -3);
+        assert_eq!(a.feed(4), -2); // -4 / 3
+        let a = a.with_rounding(Rounding::HalfUp);
+        assert_eq!(a.get(), -1);
+
+        // Floats are not affected.
+        let mut a: MovAvg<f32, f32, 2> = MovAvg::new().with_rounding(Rounding::Floor);
+        a.feed(1.0);
+        assert_eq!(a.feed(2.0), 1.5);
+    }
+
+    #[test]
+    fn test_get_as() {
+        let mut a: MovAvg<u8, u32, 3> = MovAvg::new();
+        assert_eq!(a.try_get_as::<f64>(), Err(MovAvgError::Empty));
+        a.feed(1);
+        a.feed(2);
+        assert_eq!(a.get(), 1);
+        assert_eq!(a.get_as::<f64>(), 1.5);
+        assert_eq!(a.get_as::<f32>(), 1.5);
+        assert_eq!(a.get_as::<u8>(), 1);
+        let a = a.with_rounding(Rounding::HalfUp);
+        assert_eq!(a.get(), 2);
+        assert_eq!(a.get_as::<i128>(), 2);
+        assert_eq!(a.get_as::<f64>(), 1.5);
+
+        let mut a: MovAvg<i16, i32, 2> = MovAvg::new();
+        a.feed(300);
+        a.feed(301);
+        assert_eq!(a.get_as::<f64>(), 300.5);
+        assert_eq!(a.try_get_as::<u8>(), Err(MovAvgError::CastResult));
+        a.feed(-1);
+        assert_eq!(a.try_get_as::<u8>(), Ok(150));
+        a.feed(-2);
+        assert_eq!(a.try_get_as::<u8>(), Err(MovAvgError::CastResult));
+        assert_eq!(a.get_as::<f32>(), -1.5);
+
+        let mut a: MovAvg<f32, f64, 3> = MovAvg::new();
+        a.feed(0.1);
+        a.feed(0.2);
+        assert_eq!(a.get_as::<f64>(), (0.1_f32 as f64 + 0.2_f32 as f64) / 2.0);
+        let a = a.with_rounding(Rounding::HalfUp);
+        assert_eq!(a.get(), ((0.1_f32 as f64 + 0.2_f32 as f64) / 2.0) as f32);
+
+        // Float accumulator and integer output type.
+        let check = |values: &[i32], rounding, expected: i32| {
+            let mut a: MovAvg<i32, f64, 4> = MovAvg::new().with_rounding(rounding);
+            for value in values {
+                a.feed(*value);
+            }
+            assert_eq!(a.get_as::<i32>(), expected, "{:?} {:?}", values, rounding);
+            assert_eq!(a.get(), expected, "{:?} {:?}", values, rounding);
+        };
+        check(&[1, 2, 2, 2], Rounding::Truncate, 1); // 1.75
+        check(&[1, 2, 2, 2], Rounding::Floor, 1);
+        check(&[1, 2, 2, 2], Rounding::Ceil, 2);
+        check(&[1, 2, 2, 2], Rounding::HalfUp, 2);
+        check(&[1, 2, 2, 2], Rounding::HalfEven, 2);
+        check(&[-1, -2, -2, -2], Rounding::Truncate, -1); // -1.75
+        check(&[-1, -2, -2, -2], Rounding::Floor, -2);
+        check(&[-1, -2, -2, -2], Rounding::Ceil, -1);
+        check(&[-1, -2, -2, -2], Rounding::HalfUp, -2);
+        check(&[1, 2], Rounding::HalfUp, 2); // 1.5
+        check(&[1, 2], Rounding::HalfEven, 2);
+        check(&[2, 3], Rounding::HalfEven, 2); // 2.5
+        check(&[-2, -3], Rounding::HalfUp, -2); // -2.5
+        check(&[-2, -3], Rounding::HalfEven, -2);
+        check(&[-1, -2], Rounding::HalfEven, -2); // -1.5
+        check(&[4, 4, 4, 5], Rounding::Truncate, 4); // 4.25
+        check(&[4, 4, 4, 5], Rounding::Ceil, 5);
+        check(&[4, 4, 4, 5], Rounding::HalfEven, 4);
+    }
+
+    #[test]
+    #[should_panic(expected = "The MovAvg state is empty")]
+    fn test_get_as_empty_panic() {
+        let a: MovAvg<i32, i32, 3> = MovAvg::new();
+        a.get_as::<f64>(); // this panics
+    }
+
+    #[test]
+    fn test_sum_fraction() {
+        let mut a: MovAvg<i8, i16, 3> = MovAvg::new();
+        assert_eq!(a.sum(), 0);
+        assert_eq!(a.try_get_fraction(), Err(MovAvgError::Empty));
+        a.feed(-100);
+        a.feed(-27);
+        assert_eq!(a.sum(), -127);
+        assert_eq!(a.get_fraction(), (-127, 2));
+        a.feed(100);
+        a.feed(100);
+        assert_eq!(a.sum(), 173);
+        assert_eq!(a.get_fraction(), (173, 3));
+        a.reset();
+        assert_eq!(a.sum(), 0);
+
+        let mut a: MovAvg<f64, f64, 2> = MovAvg::new().with_accu_mode(AccuMode::Compensated);
+        a.feed(1e17);
+        a.feed(1.0);
+        a.feed(3.0);
+        assert_eq!(a.get_fraction(), (4.0, 2.0));
+    }
+
+    #[test]
+    fn test_checked_subtraction_order() {
+        let mut a: MovAvg<i8, i8, 3> = MovAvg::new();
+        assert_eq!(a.overflow_policy(), OverflowPolicy::Checked);
+        a.feed(-128);
+        a.feed(127);
+        assert_eq!(a.feed(127), 42); // 126 / 3
+                                     // (126 - -128) overflows, but (126 + -127) - -128 does not.
+        assert_eq!(a.feed(-127), 42); // 127 / 3
+        assert_eq!(a.feed(127), 42); // 127 / 3
+        assert_eq!(a.feed(127), 42); // 127 / 3
+        assert_eq!(a.try_feed(127), Err(MovAvgError::AccuOverflow));
+        assert_eq!(a.get(), 42);
+    }
+
+    #[test]
+    fn test_overflow_wrapping() {
+        let mut a: MovAvg<u8, u8, 4> = MovAvg::new().with_overflow_policy(OverflowPolicy::Wrapping);
+        assert_eq!(a.overflow_policy(), OverflowPolicy::Wrapping);
+        let mut window = [0_u32; 4];
+        let mut state = 0x1357_9BDF_u32;
+        for i in 0..10_000 {
+            state ^= state << 13;
+            state ^= state >> 17;
+            state ^= state << 5;
+            // Mostly small values, sometimes big ones.
+            let value = if state % 8 == 0 {
+                state % 256
+            } else {
+                state % 64
+            };
+            window[i % 4] = value;
+            let avg = a.feed(value as u8);
+            let sum: u32 = window.iter().sum();
+            if i >= 3 && sum <= u8::MAX as u32 {
+                assert_eq!(avg as u32, sum / 4);
+            }
+        }
+
+        let mut a: MovAvg<i8, i8, 2> = MovAvg::new_init([100, 100], 1);
+        assert_eq!(a.try_feed(100), Err(MovAvgError::AccuOverflow));
+        let mut a = a.with_overflow_policy(OverflowPolicy::Wrapping);
+        a.feed(100);
+        a.feed(-100);
+        assert_eq!(a.feed(-20), -60);
+    }
+
+    #[test]
+    fn test_overflow_saturating() {
+        let mut a: MovAvg<u8, u8, 3> =
+            MovAvg::new().with_overflow_policy(OverflowPolicy::Saturating);
+        assert_eq!(a.feed(200), 200);
+        assert_eq!(a.feed(200), 200);
+        assert_eq!(a.feed(200), 200);
+        assert_eq!(a.sum(), 255);
+        assert_eq!(a.try_get_fraction(), Err(MovAvgError::AccuOverflow));
+        assert_eq!(a.get_as::<f32>(), 200.0);
+        assert_eq!(a.feed(10), 136); // 410 / 3
+        assert_eq!(a.feed(10), 73); // 220 / 3
+        assert_eq!(a.sum(), 220);
+        assert_eq!(a.get_fraction(), (220, 3));
+        assert_eq!(a.feed(10), 10);
+
+        // The average does not depend on the order of the items.
+        let mut a: MovAvg<i8, i8, 3> =
+            MovAvg::new().with_overflow_policy(OverflowPolicy::Saturating);
+        a.feed(100);
+        a.feed(100);
+        assert_eq!(a.feed(-100), 33); // 100 / 3
+        assert_eq!(a.sum(), 100);
+        assert_eq!(a.feed(10), 3); // 10 / 3
+
+        let mut a: MovAvg<i8, i8, 2> =
+            MovAvg::new().with_overflow_policy(OverflowPolicy::Saturating);
+        a.feed(-100);
+        assert_eq!(a.feed(-100), -100);
+        assert_eq!(a.sum(), -128);
+        assert_eq!(a.feed(50), -25);
+        assert_eq!(a.feed(127), 88); // 177 / 2
+        assert_eq!(a.sum(), 127);
+
+        // Rounding of the average.
+        let mut a: MovAvg<i8, i8, 2> = MovAvg::new()
+            .with_overflow_policy(OverflowPolicy::Saturating)
+            .with_rounding(Rounding::HalfEven);
+        a.feed(-101);
+        assert_eq!(a.feed(-100), -100); // -100.5
+        assert_eq!(a.feed(-99), -100); // -99.5
+        let a = a.with_rounding(Rounding::Floor);
+        assert_eq!(a.get(), -100);
+        let a = a.with_rounding(Rounding::Ceil);
+        assert_eq!(a.get(), -99);
+        let a = a.with_rounding(Rounding::HalfUp);
+        assert_eq!(a.get(), -99);
+        let a = a.with_rounding(Rounding::Truncate);
+        assert_eq!(a.get(), -99);
+        assert_eq!(a.get_as::<f64>(), -99.5);
+
+        // Initialization with an overflowing buffer.
+        let a: MovAvg<u8, u8, 2> = MovAvg::new_init([200, 200], 1);
+        let mut a = a.with_overflow_policy(OverflowPolicy::Saturating);
+        assert_eq!(a.feed(200), 200);
+        assert_eq!(a.sum(), 255);
+        let a = a.with_overflow_policy(OverflowPolicy::Wrapping);
+        assert_eq!(a.sum(), 144);
+
+        // Floats are not affected.
+        let mut a: MovAvg<f32, f32, 2> =
+            MovAvg::new().with_overflow_policy(OverflowPolicy::Saturating);
+        a.feed(f32::MAX);
+        assert_eq!(a.feed(f32::MAX), f32::INFINITY);
+    }
+
+    #[test]
+    fn test_feed_slice() {
+        let mut values = [0_i32; 256];
+        for (i, value) in values.iter_mut().enumerate() {
+            *value = ((i * 37) % 101) as i32 - 50;
+        }
+        let mut single: MovAvg<i32, i32, 7> = MovAvg::new();
+        let mut expected = [0; 256];
+        for (value, avg) in values.iter().zip(expected.iter_mut()) {
+            *avg = single.feed(*value);
+        }
+
+        let mut a: MovAvg<i32, i32, 7> = MovAvg::new();
+        assert_eq!(a.feed_slice(&values[..100]), expected[99]);
+        assert_eq!(a.feed_slice(&values[100..]), expected[255]);
+        assert_eq!(a.feed_slice(&[]), expected[255]);
+        assert_eq!(a.sum(), single.sum());
+
+        let mut a: MovAvg<i32, i32, 7> = MovAvg::new();
+        assert_eq!(a.extend(values.iter().copied()), expected[255]);
+
+        let mut a: MovAvg<i32, i32, 7> = MovAvg::new();
+        let mut out = [0; 256];
+        a.feed_slice_into(&values[..3], &mut out[..3]);
+        a.feed_slice_into(&values[3..], &mut out[3..]);
+        assert_eq!(out, expected);
+
+        let mut empty: MovAvg<i32, i32, 7> = MovAvg::new();
+        assert_eq!(empty.try_feed_slice(&[]), Err(MovAvgError::Empty));
+        assert_eq!(empty.try_extend(None), Err(MovAvgError::Empty));
+    }
+
+    #[test]
+    fn test_feed_slice_error() {
+        let mut a: MovAvg<u8, u8, 3> = MovAvg::new();
+        assert_eq!(
+            a.try_feed_slice(&[10, 20, 250, 30]),
+            Err(MovAvgError::AccuOverflow)
+        );
+        assert_eq!(a.len(), 2);
+        assert_eq!(a.get(), 15);
+
+        let mut a: MovAvg<u8, u8, 3> = MovAvg::new();
+        let mut out = [0; 4];
+        assert_eq!(
+            a.try_feed_slice_into(&[10, 20, 250, 30], &mut out),
+            Err(MovAvgError::AccuOverflow)
+        );
+        assert_eq!(out, [10, 15, 0, 0]);
+        assert_eq!(a.get(), 15);
+    }
+
+    #[test]
+    fn test_feed_slice_into_len() {
+        let mut a: MovAvg<u8, u8, 3> = MovAvg::new();
+        let mut out = [0; 1];
+        assert_eq!(
+            a.try_feed_slice_into(&[1, 2], &mut out),
+            Err(MovAvgError::BufferTooSmall)
+        );
+        assert!(a.is_empty());
+        let mut out = [0; 3];
+        a.feed_slice_into(&[1, 2], &mut out);
+        assert_eq!(out, [1, 1, 0]);
+    }
+
+    #[test]
+    fn test_compensated_int() {
+        let mut a: MovAvg<i32, i32, 3> = MovAvg::new_init([10, 20, 0], 2);
+        let mut a = {
+            a.feed(30);
+            a.with_accu_mode(AccuMode::Compensated)
+        };
+        assert_eq!(a.get(), 20);
+        assert_eq!(a.feed(40), 30);
+        assert_eq!(a.feed(-70), 0);
+        let mut a: MovAvg<u8, u8, 2> = MovAvg::new().with_accu_mode(AccuMode::Compensated);
+        a.feed(200);
+        assert_eq!(a.try_feed(100), Err(MovAvgError::AccuOverflow));
+        assert_eq!(a.get(), 200);
+    }
+
+    #[test]
+    fn test_window_iter() {
+        let mut a: MovAvg<i32, i32, 4> = MovAvg::new();
+        assert_eq!(a.iter().len(), 0);
+        assert_eq!(a.oldest(), None);
+        assert_eq!(a.newest(), None);
+        assert_eq!(a.as_slices(), (&[][..], &[][..]));
+        for i in 1..=10 {
+            a.feed(i);
+            let first = (i - 3).max(1);
+            assert!(a.iter().eq(first..=i));
+            assert!(a.iter().rev().eq((first..=i).rev()));
+            assert_eq!(a.iter().len(), a.len());
+            assert_eq!(a.oldest(), Some(first));
+            assert_eq!(a.newest(), Some(i));
+            let (older, newer) = a.as_slices();
+            assert!(older.iter().chain(newer.iter()).copied().eq(first..=i));
+        }
+
+        // Failed feeds do not modify the window.
+        let mut a: MovAvg<u8, u8, 3> = MovAvg::new_init([100, 0, 0], 1);
+        assert_eq!(a.as_slices(), (&[100][..], &[][..]));
+        assert_eq!(a.try_feed(200), Err(MovAvgError::AccuOverflow));
+        assert!(a.iter().eq([100]));
+        a.feed(50);
+        a.feed(1);
+        a.feed(2);
+        assert_eq!(a.as_slices(), (&[50, 1][..], &[2][..]));
+        a.reset();
+        assert_eq!(a.iter().next(), None);
+
+        // Fully populated on construction.
+        let a: MovAvg<u8, u8, 3> = MovAvg::new_init([1, 2, 3], 3);
+        assert_eq!(a.as_slices(), (&[1, 2, 3][..], &[][..]));
+        assert_eq!((a.oldest(), a.newest()), (Some(1), Some(3)));
+    }
 }
 
 // vim: ts=4 sw=4 expandtab
@@ -0,0 +1,472 @@
+// -*- coding: utf-8 -*-
+//
+// Copyright 2026 Michael Büsch <m@bues.ch>
+//
+// Licensed under the Apache License version 2.0
+// or the MIT license, at your option.
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//
+
+use crate::{
+    error::MovAvgError,
+    sma::{impl_sma_methods, AccuMode, MovAvgAccu, OverflowPolicy, Rounding, SmaState, WindowIter},
+};
+use alloc::{vec, vec::Vec};
+use num_traits::{Num, NumCast};
+
+/// Simple Moving Average (SMA) with a window size chosen at runtime.
+///
+/// This is the heap allocated variant of [MovAvg](crate::MovAvg).
+/// It is only available, if the `alloc` feature is enabled.
+///
+/// # Examples
+///
+/// ```
+/// use movavg::DynMovAvg;
+///
+/// let mut avg: DynMovAvg<i32, i32> = DynMovAvg::new(3); // window size = 3
+/// assert_eq!(avg.feed(10), 10);
+/// assert_eq!(avg.feed(20), 15);
+/// assert_eq!(avg.feed(30), 20);
+/// assert_eq!(avg.feed(40), 30);
+/// assert_eq!(avg.get(), 30);
+/// ```
+///
+/// # Type Generics
+///
+/// `struct DynMovAvg<T, A>`
+///
+/// * `T` - The type of the `feed()` input value.
+/// * `A` - The type of the internal accumulator.
+///   This type must be bigger then or equal to `T`.
+#[derive(Clone, Debug)]
+pub struct DynMovAvg<T, A> {
+    buffer: Vec<T>,
+    state: SmaState<A>,
+}
+
+impl<T, A> DynMovAvg<T, A>
+where
+    T: Num + NumCast + Copy,
+    A: Num + NumCast + Copy + MovAvgAccu<T>,
+{
+    /// Construct a new Simple Moving Average with a window of `window_size` elements.
+    ///
+    /// The internal accumulator defaults to zero.
+    ///
+    /// # Panics
+    ///
+    /// Panics, if `window_size` is zero.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use movavg::DynMovAvg;
+    ///
+    /// let mut avg: DynMovAvg<i32, i32> = DynMovAvg::new(3); // window size = 3
+    /// assert_eq!(avg.feed(10), 10);
+    /// ```
+    pub fn new(window_size: usize) -> DynMovAvg<T, A> {
+        assert!(window_size > 0);
+        Self::new_init(vec![T::one(); window_size], 0)
+    }
+
+    /// Try to construct a new Simple Moving Average with a window of `window_size` elements.
+    ///
+    /// This is the same as [DynMovAvg::new], but it returns a `Result`
+    /// instead of panicking.
+    ///
+    /// Returns `Err`, if `window_size` is zero. ([MovAvgError::InvalidWindowSize])
+    pub fn try_new(window_size: usize) -> Result<DynMovAvg<T, A>, MovAvgError> {
+        Self::try_new_init(vec![T::one(); window_size], 0)
+    }
+
+    /// Construct a new Simple Moving Average from a pre-allocated buffer
+    /// and initialize its internal state.
+    ///
+    /// * `buffer` - (Partially) pre-populated window buffer. Contains the window values.
+    ///   The length of this buffer defines the Moving Average window size.
+    /// * `nr_populated` - The number of pre-populated Moving Average window elements in `buffer`.
+    ///   `nr_populated` must be less than or equal to `buffer.len()`.
+    ///   The populated values in `buffer` must begin at index 0.
+    ///   The values of unpopulated elements in `buffer` does not matter.
+    ///
+    /// # Panics
+    ///
+    /// Panics, if:
+    /// * `buffer` is empty.
+    /// * `nr_populated` is bigger than `buffer.len()`.
+    /// * The initial accumulator calculation fails. (e.g. due to overflow).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use movavg::DynMovAvg;
+    ///
+    /// let mut avg: DynMovAvg<i32, i32> = DynMovAvg::new_init(vec![10, 20, 30, 0, 0], 3);
+    ///
+    /// assert_eq!(avg.get(), 20);
+    /// assert_eq!(avg.feed(60), 30);
+    /// ```
+    pub fn new_init(buffer: Vec<T>, nr_populated: usize) -> DynMovAvg<T, A> {
+        let state = SmaState::new_init(&buffer, nr_populated);
+        DynMovAvg { buffer, state }
+    }
+
+    /// Try to construct a new Simple Moving Average from a pre-allocated buffer
+    /// and initialize its internal state.
+    ///
+    /// This is the same as [DynMovAvg::new_init], but it returns a `Result`
+    /// instead of panicking.
+    ///
+    /// Returns `Err`, if:
+    /// * `buffer` is empty. ([MovAvgError::InvalidWindowSize])
+    /// * `nr_populated` is bigger than `buffer.len()`.
+    ///   ([MovAvgError::InvalidNrPopulated])
+    /// * The initial accumulator calculation fails. (e.g. due to overflow).
+    pub fn try_new_init(
+        buffer: Vec<T>,
+        nr_populated: usize,
+    ) -> Result<DynMovAvg<T, A>, MovAvgError> {
+        let state = SmaState::try_new_init(&buffer, nr_populated)?;
+        Ok(DynMovAvg { buffer, state })
+    }
+
+    /// Get the current length of the Moving Average window.
+    ///
+    /// This length is in the range of `0..window_size`.
+    /// If the length is less than the window size, then the Moving Average window
+    /// has not been fully populated, yet.
+    ///
+    /// See [Self::window_size] to get the nominal maximum length of the window.
+    #[inline]
+    pub const fn len(&self) -> usize {
+        self.state.len()
+    }
+
+    /// Get the nominal size of the Moving Average window.
+    ///
+    /// See [Self::len] to get the actual current length of the window.
+    #[inline]
+    pub fn window_size(&self) -> usize {
+        self.buffer.len()
+    }
+
+    /// Change the nominal size of the Moving Average window to `new_size`.
+    ///
+    /// If the window shrinks, then the oldest items are dropped
+    /// and the newest `new_size` items are kept.
+    /// The accumulator is recalculated from the kept items.
+    ///
+    /// If the window grows, then all items are kept.
+    /// The window is then partially populated and [Self::len] stays unchanged.
+    ///
+    /// # Panics
+    ///
+    /// Panics, if:
+    /// * `new_size` is zero.
+    /// * The accumulator recalculation fails. (e.g. due to overflow).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use movavg::DynMovAvg;
+    ///
+    /// let mut avg: DynMovAvg<i32, i32> = DynMovAvg::new(3);
+    /// assert_eq!(avg.feed(10), 10);
+    /// assert_eq!(avg.feed(20), 15);
+    /// assert_eq!(avg.feed(30), 20);
+    ///
+    /// avg.resize(2); // Drops the oldest item 10.
+    /// assert_eq!(avg.len(), 2);
+    /// assert_eq!(avg.get(), 25);
+    ///
+    /// avg.resize(4);
+    /// assert_eq!(avg.len(), 2);
+    /// assert_eq!(avg.feed(40), 30);
+    /// ```
+    pub fn resize(&mut self, new_size: usize) {
+        self.try_resize(new_size)
+            .expect("Failed to resize the MovAvg.");
+    }
+
+    /// Try to change the nominal size of the Moving Average window to `new_size`.
+    ///
+    /// This is the same as [DynMovAvg::resize], but it returns a `Result`
+    /// instead of panicking.
+    /// The Moving Average is not modified, if an error is returned.
+    ///
+    /// Returns `Err`, if:
+    /// * `new_size` is zero. ([MovAvgError::InvalidWindowSize])
+    /// * The accumulator recalculation fails. (e.g. due to overflow).
+    pub fn try_resize(&mut self, new_size: usize) -> Result<(), MovAvgError> {
+        if new_size == 0 {
+            return Err(MovAvgError::InvalidWindowSize);
+        }
+
+        // Copy the kept items in chronological order, oldest item first.
+        let nr_items = self.len().min(new_size);
+        let mut buffer = Vec::with_capacity(new_size);
+        buffer.extend(self.iter().skip(self.len() - nr_items));
+        buffer.resize(new_size, T::one());
+
+        // This only modifies the state, if the recalculation succeeds.
+        self.state.reinit(&buffer, nr_items)?;
+        self.buffer = buffer;
+        Ok(())
+    }
+
+    impl_sma_methods! {
+        name: "DynMovAvg",
+        with_accu_mode: {},
+        with_rounding: {},
+        with_overflow_policy: {},
+        try_feed_slice: {},
+        try_get_as: {},
+        sum: {},
+        iter: {
+            ///
+            /// # Examples
+            ///
+            /// ```
+            /// use movavg::DynMovAvg;
+            ///
+            /// let mut avg: DynMovAvg<i32, i32> = DynMovAvg::new_init(vec![10, 20, 0], 2);
+            /// assert!(avg.iter().eq([10, 20]));
+            /// avg.feed(30);
+            /// avg.feed(40); // 10 is evicted
+            /// assert!(avg.iter().eq([20, 30, 40]));
+            /// avg.resize(2); // 20 is evicted
+            /// assert!(avg.iter().eq([30, 40]));
+            /// ```
+        },
+        as_slices: {
+            ///
+            /// # Examples
+            ///
+            /// ```
+            /// use movavg::DynMovAvg;
+            ///
+            /// let mut avg: DynMovAvg<i32, i32> = DynMovAvg::new(3);
+            /// avg.feed(1);
+            /// avg.feed(2);
+            /// assert_eq!(avg.as_slices(), (&[1, 2][..], &[][..]));
+            /// avg.feed(3);
+            /// avg.feed(4);
+            /// assert_eq!(avg.as_slices(), (&[2, 3][..], &[4][..]));
+            /// ```
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_i32() {
+        let mut a: DynMovAvg<i32, i32> = DynMovAvg::new(5);
+        assert_eq!(a.window_size(), 5);
+        assert_eq!(a.feed(10), 10);
+        assert_eq!(a.feed(20), (10 + 20) / 2);
+        assert_eq!(a.feed(2), (10 + 20 + 2) / 3);
+        assert_eq!(a.feed(100), (10 + 20 + 2 + 100) / 4);
+        assert_eq!(a.feed(111), (10 + 20 + 2 + 100 + 111) / 5);
+        assert_eq!(a.feed(200), (20 + 2 + 100 + 111 + 200) / 5);
+        assert_eq!(a.feed(250), (2 + 100 + 111 + 200 + 250) / 5);
+        assert_eq!(a.feed(-25), (100 + 111 + 200 + 250 - 25) / 5);
+        assert_eq!(a.feed(-100_000), (111 + 200 + 250 - 25 - 100_000) / 5);
+        assert_eq!(a.len(), 5);
+    }
+
+    #[test]
+    fn test_f64() {
+        let mut a: DynMovAvg<f64, f64> = DynMovAvg::new(3);
+        let e = 0.000001;
+        assert!((a.feed(10.0) - 10.0).abs() < e);
+        assert!((a.feed(20.0) - ((10.0 + 20.0) / 2.0)).abs() < e);
+        assert!((a.feed(2.0) - ((10.0 + 20.0 + 2.0) / 3.0)).abs() < e);
+        assert!((a.feed(-100.0) - ((20.0 + 2.0 - 100.0) / 3.0)).abs() < e);
+    }
+
+    #[test]
+    fn test_accu_overflow() {
+        let mut a: DynMovAvg<u8, u8> = DynMovAvg::new(3);
+        a.feed(200);
+        assert!(a.try_feed(200).is_err());
+        assert_eq!(a.len(), 1);
+        assert_eq!(a.get(), 200);
+    }
+
+    #[test]
+    fn test_init() {
+        let mut a: DynMovAvg<i32, i32> = DynMovAvg::new_init(vec![10, 99, 99], 1);
+        assert_eq!(a.window_size(), 3);
+        assert_eq!(a.len(), 1);
+        assert_eq!(a.feed(20), 15);
+        assert_eq!(a.feed(102), 44);
+        assert_eq!(a.feed(178), 100);
+        assert_eq!(a.len(), 3);
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut a: DynMovAvg<i32, i32> = DynMovAvg::new(2);
+        assert_eq!(a.feed(10), 10);
+        assert_eq!(a.feed(20), 15);
+        a.reset();
+        assert!(a.is_empty());
+        assert!(a.try_get().is_err());
+        assert_eq!(a.feed(40), 40);
+        assert_eq!(a.feed(60), 50);
+    }
+
+    #[test]
+    fn test_resize() {
+        let mut a: DynMovAvg<i32, i32> = DynMovAvg::new(4);
+        assert_eq!(a.feed(10), 10);
+        assert_eq!(a.feed(20), 15);
+        assert_eq!(a.feed(30), 20);
+        assert_eq!(a.feed(40), 25);
+        assert_eq!(a.feed(50), 35);
+
+        // Shrink a full and wrapped window.
+        a.resize(3);
+        assert_eq!(a.window_size(), 3);
+        assert_eq!(a.len(), 3);
+        assert_eq!(a.get(), (30 + 40 + 50) / 3);
+        assert_eq!(a.feed(60), (40 + 50 + 60) / 3);
+
+        // Grow.
+        a.resize(5);
+        assert_eq!(a.window_size(), 5);
+        assert_eq!(a.len(), 3);
+        assert_eq!(a.get(), (40 + 50 + 60) / 3);
+        assert_eq!(a.feed(70), (40 + 50 + 60 + 70) / 4);
+        assert_eq!(a.feed(80), (40 + 50 + 60 + 70 + 80) / 5);
+        assert_eq!(a.feed(90), (50 + 60 + 70 + 80 + 90) / 5);
+        assert_eq!(a.len(), 5);
+
+        // Shrink a partially populated window.
+        let mut a: DynMovAvg<i32, i32> = DynMovAvg::new(5);
+        assert_eq!(a.feed(10), 10);
+        assert_eq!(a.feed(20), 15);
+        a.resize(4);
+        assert_eq!(a.len(), 2);
+        assert_eq!(a.get(), 15);
+        a.resize(1);
+        assert_eq!(a.len(), 1);
+        assert_eq!(a.get(), 20);
+        assert_eq!(a.feed(30), 30);
+
+        // Resize an empty window.
+        let mut a: DynMovAvg<i32, i32> = DynMovAvg::new(2);
+        a.resize(3);
+        assert!(a.is_empty());
+        assert_eq!(a.feed(10), 10);
+    }
+
+    #[test]
+    fn test_try_resize() {
+        let mut a: DynMovAvg<i8, i8> = DynMovAvg::new(3);
+        a.feed(-100);
+        a.feed(100);
+        a.feed(100);
+
+        // The sum of the kept items 100 + 100 does not fit into i8.
+        assert_eq!(a.try_resize(2), Err(MovAvgError::AccuOverflow));
+        assert_eq!(a.window_size(), 3);
+        assert_eq!(a.len(), 3);
+        assert!(a.iter().eq([-100, 100, 100]));
+        assert_eq!(a.get(), 33);
+
+        assert_eq!(a.try_resize(0), Err(MovAvgError::InvalidWindowSize));
+        assert_eq!(a.window_size(), 3);
+
+        assert_eq!(a.try_resize(1), Ok(()));
+        assert_eq!(a.window_size(), 1);
+        assert_eq!(a.get(), 100);
+    }
+
+    #[test]
+    #[should_panic(expected = "Failed to resize the MovAvg")]
+    fn test_resize_panic() {
+        let mut a: DynMovAvg<i32, i32> = DynMovAvg::new(3);
+        a.resize(0); // this panics
+    }
+
+    #[test]
+    fn test_window_iter() {
+        let mut a: DynMovAvg<i32, i32> = DynMovAvg::new(3);
+        for i in 1..=5 {
+            a.feed(i);
+        }
+        assert_eq!(a.as_slices(), (&[3][..], &[4, 5][..]));
+        a.resize(5);
+        assert_eq!(a.as_slices(), (&[3, 4, 5][..], &[][..]));
+        a.feed(6);
+        assert!(a.iter().eq(3..=6));
+        a.resize(2);
+        assert_eq!((a.oldest(), a.newest()), (Some(5), Some(6)));
+    }
+
+    #[test]
+    fn test_get_as() {
+        let mut a: DynMovAvg<u8, u16> = DynMovAvg::new(4);
+        a.feed(255);
+        a.feed(0);
+        a.feed(0);
+        assert_eq!(a.get(), 85);
+        assert_eq!(a.get_as::<f64>(), 85.0);
+        a.feed(0);
+        assert_eq!(a.get_as::<f64>(), 63.75);
+    }
+
+    #[test]
+    fn test_rounding() {
+        let mut a: DynMovAvg<u8, u16> = DynMovAvg::new(2).with_rounding(Rounding::HalfUp);
+        a.feed(10);
+        assert_eq!(a.feed(11), 11);
+        a.resize(3);
+        assert_eq!(a.rounding(), Rounding::HalfUp);
+        assert_eq!(a.feed(12), 11);
+        assert_eq!(a.feed(14), 12); // 12.33
+    }
+
+    #[test]
+    fn test_accu_mode() {
+        let mut a: DynMovAvg<f64, f64> = DynMovAvg::new(3).with_accu_mode(AccuMode::Compensated);
+        assert_eq!(a.accu_mode(), AccuMode::Compensated);
+        a.feed(1e16);
+        a.feed(1.0);
+        a.feed(2.0);
+        a.resize(4);
+        assert_eq!(a.accu_mode(), AccuMode::Compensated);
+        assert_eq!(a.rounding(), Rounding::Truncate);
+        a.feed(3.0);
+        assert_eq!(a.feed(4.0), (1.0 + 2.0 + 3.0 + 4.0) / 4.0);
+    }
+
+    #[test]
+    fn test_try_new() {
+        let a: Result<DynMovAvg<i32, i32>, _> = DynMovAvg::try_new(0);
+        assert_eq!(a.unwrap_err(), MovAvgError::InvalidWindowSize);
+
+        let a: Result<DynMovAvg<i32, i32>, _> = DynMovAvg::try_new_init(vec![1, 2], 3);
+        assert_eq!(a.unwrap_err(), MovAvgError::InvalidNrPopulated);
+
+        let a: Result<DynMovAvg<u8, u8>, _> = DynMovAvg::try_new_init(vec![200, 200], 2);
+        assert_eq!(a.unwrap_err(), MovAvgError::AccuOverflow);
+
+        let a: DynMovAvg<i32, i32> = DynMovAvg::try_new(2).unwrap();
+        assert!(a.is_empty());
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_zero_window_size() {
+        let _a: DynMovAvg<i32, i32> = DynMovAvg::new(0); // this panics
+    }
+}
+
+// vim: ts=4 sw=4 expandtab
@@ -0,0 +1,319 @@
+// -*- coding: utf-8 -*-
+//
+// Copyright 2026 Michael Büsch <m@bues.ch>
+//
+// Licensed under the Apache License version 2.0
+// or the MIT license, at your option.
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//
+
+use crate::{
+    error::MovAvgError,
+    sma::{impl_sma_methods, AccuMode, MovAvgAccu, OverflowPolicy, Rounding, SmaState, WindowIter},
+};
+use num_traits::{Num, NumCast};
+
+/// Simple Moving Average (SMA) operating on a window buffer borrowed from the caller.
+///
+/// This is the variant of [MovAvg](crate::MovAvg) that does not own its window buffer.
+/// The window size is defined by the length of the borrowed buffer.
+///
+/// # Examples
+///
+/// ```
+/// use movavg::MovAvgRef;
+///
+/// let mut buf = [0_i32; 3]; // window size = 3
+/// let mut avg: MovAvgRef<i32, i32> = MovAvgRef::new(&mut buf);
+/// assert_eq!(avg.feed(10), 10);
+/// assert_eq!(avg.feed(20), 15);
+/// assert_eq!(avg.feed(30), 20);
+/// assert_eq!(avg.feed(40), 30);
+/// assert_eq!(avg.get(), 30);
+/// ```
+///
+/// # Type Generics
+///
+/// `struct MovAvgRef<'a, T, A>`
+///
+/// * `'a` - The lifetime of the borrowed window buffer.
+/// * `T` - The type of the `feed()` input value.
+/// * `A` - The type of the internal accumulator.
+///   This type must be bigger then or equal to `T`.
+#[derive(Debug)]
+pub struct MovAvgRef<'a, T, A> {
+    buffer: &'a mut [T],
+    state: SmaState<A>,
+}
+
+impl<'a, T, A> MovAvgRef<'a, T, A>
+where
+    T: Num + NumCast + Copy,
+    A: Num + NumCast + Copy + MovAvgAccu<T>,
+{
+    /// Construct a new Simple Moving Average on the borrowed window `buffer`.
+    ///
+    /// The contents of `buffer` do not matter.
+    /// The internal accumulator defaults to zero.
+    ///
+    /// # Panics
+    ///
+    /// Panics, if `buffer` is empty.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use movavg::MovAvgRef;
+    ///
+    /// let mut buf = [0_i32; 3]; // window size = 3
+    /// let mut avg: MovAvgRef<i32, i32> = MovAvgRef::new(&mut buf);
+    /// assert_eq!(avg.feed(10), 10);
+    /// ```
+    pub fn new(buffer: &'a mut [T]) -> MovAvgRef<'a, T, A> {
+        Self::new_init(buffer, 0)
+    }
+
+    /// Try to construct a new Simple Moving Average on the borrowed window `buffer`.
+    ///
+    /// This is the same as [MovAvgRef::new], but it returns a `Result`
+    /// instead of panicking.
+    ///
+    /// Returns `Err`, if `buffer` is empty. ([MovAvgError::InvalidWindowSize])
+    pub fn try_new(buffer: &'a mut [T]) -> Result<MovAvgRef<'a, T, A>, MovAvgError> {
+        Self::try_new_init(buffer, 0)
+    }
+
+    /// Construct a new Simple Moving Average on the borrowed window `buffer`
+    /// and initialize its internal state.
+    ///
+    /// * `buffer` - (Partially) pre-populated window buffer. Contains the window values.
+    ///   The length of this buffer defines the Moving Average window size.
+    /// * `nr_populated` - The number of pre-populated Moving Average window elements in `buffer`.
+    ///   `nr_populated` must be less than or equal to `buffer.len()`.
+    ///   The populated values in `buffer` must begin at index 0.
+    ///   The values of unpopulated elements in `buffer` does not matter.
+    ///
+    /// # Panics
+    ///
+    /// Panics, if:
+    /// * `buffer` is empty.
+    /// * `nr_populated` is bigger than `buffer.len()`.
+    /// * The initial accumulator calculation fails. (e.g. due to overflow).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use movavg::MovAvgRef;
+    ///
+    /// let mut buf = [10, 20, 30,  // populated
+    ///                0, 0];       // unpopulated
+    ///
+    /// let mut avg: MovAvgRef<i32, i32> =
+    ///     MovAvgRef::new_init(&mut buf,   // Borrow the buffer.
+    ///                         3);         // The first three elements of buf are pre-populated.
+    ///
+    /// assert_eq!(avg.get(), 20);
+    /// assert_eq!(avg.feed(60), 30);
+    /// ```
+    pub fn new_init(buffer: &'a mut [T], nr_populated: usize) -> MovAvgRef<'a, T, A> {
+        let state = SmaState::new_init(buffer, nr_populated);
+        MovAvgRef { buffer, state }
+    }
+
+    /// Try to construct a new Simple Moving Average on the borrowed window `buffer`
+    /// and initialize its internal state.
+    ///
+    /// This is the same as [MovAvgRef::new_init], but it returns a `Result`
+    /// instead of panicking.
+    ///
+    /// Returns `Err`, if:
+    /// * `buffer` is empty. ([MovAvgError::InvalidWindowSize])
+    /// * `nr_populated` is bigger than `buffer.len()`.
+    ///   ([MovAvgError::InvalidNrPopulated])
+    /// * The initial accumulator calculation fails. (e.g. due to overflow).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use movavg::{MovAvgError, MovAvgRef};
+    ///
+    /// let mut buf = [100_u8, 200, 0];
+    /// let avg: Result<MovAvgRef<u8, u8>, _> = MovAvgRef::try_new_init(&mut buf, 2);
+    /// assert_eq!(avg.unwrap_err(), MovAvgError::AccuOverflow);
+    /// ```
+    pub fn try_new_init(
+        buffer: &'a mut [T],
+        nr_populated: usize,
+    ) -> Result<MovAvgRef<'a, T, A>, MovAvgError> {
+        let state = SmaState::try_new_init(buffer, nr_populated)?;
+        Ok(MovAvgRef { buffer, state })
+    }
+
+    /// Get the current length of the Moving Average window.
+    ///
+    /// This length is in the range of `0..window_size`.
+    /// If the length is less than the window size, then the Moving Average window
+    /// has not been fully populated, yet.
+    ///
+    /// See [Self::window_size] to get the nominal maximum length of the window.
+    #[inline]
+    pub const fn len(&self) -> usize {
+        self.state.len()
+    }
+
+    /// Get the nominal size of the Moving Average window.
+    ///
+    /// This is always equal to the length of the borrowed buffer.
+    ///
+    /// See [Self::len] to get the actual current length of the window.
+    #[inline]
+    pub const fn window_size(&self) -> usize {
+        self.buffer.len()
+    }
+
+    impl_sma_methods! {
+        name: "MovAvgRef",
+        with_accu_mode: {},
+        with_rounding: {},
+        with_overflow_policy: {},
+        try_feed_slice: {},
+        try_get_as: {},
+        sum: {},
+        iter: {
+            ///
+            /// # Examples
+            ///
+            /// ```
+            /// use movavg::MovAvgRef;
+            ///
+            /// let mut buf = [10, 20, 0];
+            /// let mut avg: MovAvgRef<i32, i32> = MovAvgRef::new_init(&mut buf, 2);
+            /// assert!(avg.iter().eq([10, 20]));
+            /// avg.feed(30);
+            /// avg.feed(40); // 10 is evicted
+            /// assert!(avg.iter().eq([20, 30, 40]));
+            /// ```
+        },
+        as_slices: {
+            ///
+            /// # Examples
+            ///
+            /// ```
+            /// use movavg::MovAvgRef;
+            ///
+            /// let mut buf = [0; 3];
+            /// let mut avg: MovAvgRef<i32, i32> = MovAvgRef::new(&mut buf);
+            /// avg.feed(1);
+            /// avg.feed(2);
+            /// assert_eq!(avg.as_slices(), (&[1, 2][..], &[][..]));
+            /// avg.feed(3);
+            /// avg.feed(4);
+            /// assert_eq!(avg.as_slices(), (&[2, 3][..], &[4][..]));
+            /// ```
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_i32() {
+        let mut buf = [0; 5];
+        let mut a: MovAvgRef<i32, i32> = MovAvgRef::new(&mut buf);
+        assert_eq!(a.window_size(), 5);
+        assert_eq!(a.feed(10), 10);
+        assert_eq!(a.feed(20), (10 + 20) / 2);
+        assert_eq!(a.feed(2), (10 + 20 + 2) / 3);
+        assert_eq!(a.feed(100), (10 + 20 + 2 + 100) / 4);
+        assert_eq!(a.feed(111), (10 + 20 + 2 + 100 + 111) / 5);
+        assert_eq!(a.feed(200), (20 + 2 + 100 + 111 + 200) / 5);
+        assert_eq!(a.feed(250), (2 + 100 + 111 + 200 + 250) / 5);
+        assert_eq!(a.feed(-25), (100 + 111 + 200 + 250 - 25) / 5);
+        assert_eq!(a.feed(-100_000), (111 + 200 + 250 - 25 - 100_000) / 5);
+        assert_eq!(a.len(), 5);
+    }
+
+    #[test]
+    fn test_f64() {
+        let mut buf = [0.0; 3];
+        let mut a: MovAvgRef<f64, f64> = MovAvgRef::new(&mut buf);
+        let e = 0.000001;
+        assert!((a.feed(10.0) - 10.0).abs() < e);
+        assert!((a.feed(20.0) - ((10.0 + 20.0) / 2.0)).abs() < e);
+        assert!((a.feed(2.0) - ((10.0 + 20.0 + 2.0) / 3.0)).abs() < e);
+        assert!((a.feed(-100.0) - ((20.0 + 2.0 - 100.0) / 3.0)).abs() < e);
+    }
+
+    #[test]
+    fn test_sum_fraction() {
+        let mut buf = [0_u32; 4];
+        let mut a: MovAvgRef<u32, u64> = MovAvgRef::new(&mut buf);
+        a.feed(u32::MAX);
+        a.feed(u32::MAX);
+        assert_eq!(a.sum(), 2 * u32::MAX as u64);
+        assert_eq!(a.get_fraction(), (2 * u32::MAX as u64, 2));
+        assert_eq!(a.get_as::<f64>(), u32::MAX as f64);
+    }
+
+    #[test]
+    fn test_accu_overflow() {
+        let mut buf = [0; 3];
+        let mut a: MovAvgRef<u8, u8> = MovAvgRef::new(&mut buf);
+        a.feed(200);
+        assert!(a.try_feed(200).is_err());
+        assert_eq!(a.len(), 1);
+        assert_eq!(a.get(), 200);
+    }
+
+    #[test]
+    fn test_init() {
+        let mut buf = [10, 99, 99];
+        let mut a: MovAvgRef<i32, i32> = MovAvgRef::new_init(&mut buf, 1);
+        assert_eq!(a.window_size(), 3);
+        assert_eq!(a.len(), 1);
+        assert_eq!(a.feed(20), 15);
+        assert_eq!(a.feed(102), 44);
+        assert_eq!(a.feed(178), 100);
+        assert_eq!(a.len(), 3);
+        assert_eq!(buf, [178, 20, 102]);
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut buf = [0; 2];
+        let mut a: MovAvgRef<i32, i32> = MovAvgRef::new(&mut buf);
+        assert_eq!(a.feed(10), 10);
+        assert_eq!(a.feed(20), 15);
+        a.reset();
+        assert!(a.is_empty());
+        assert!(a.try_get().is_err());
+        assert_eq!(a.feed(40), 40);
+        assert_eq!(a.feed(60), 50);
+    }
+
+    #[test]
+    fn test_try_new() {
+        let mut buf: [i32; 0] = [];
+        let a: Result<MovAvgRef<i32, i32>, _> = MovAvgRef::try_new(&mut buf);
+        assert_eq!(a.unwrap_err(), MovAvgError::InvalidWindowSize);
+
+        let mut buf = [1, 2];
+        let a: Result<MovAvgRef<i32, i32>, _> = MovAvgRef::try_new_init(&mut buf, 3);
+        assert_eq!(a.unwrap_err(), MovAvgError::InvalidNrPopulated);
+
+        let mut buf = [1, 2];
+        let a: MovAvgRef<i32, i32> = MovAvgRef::try_new_init(&mut buf, 2).unwrap();
+        assert_eq!(a.get(), 1);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_empty_buffer() {
+        let mut buf: [i32; 0] = [];
+        let _a: MovAvgRef<i32, i32> = MovAvgRef::new(&mut buf); // this panics
+    }
+}
+
+// vim: ts=4 sw=4 expandtab
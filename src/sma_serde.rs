@@ -0,0 +1,275 @@
+// -*- coding: utf-8 -*-
+//
+// Copyright 2026 Michael Büsch <m@bues.ch>
+//
+// Licensed under the Apache License version 2.0
+// or the MIT license, at your option.
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//
+
+use crate::sma::{AccuMode, MovAvg, MovAvgAccu, OverflowPolicy, Rounding, SmaState};
+use core::{fmt, marker::PhantomData};
+use num_traits::{Num, NumCast};
+use serde::{
+    de::{self, Deserializer, SeqAccess, Visitor},
+    ser::{SerializeStruct, SerializeTuple, Serializer},
+    Deserialize, Serialize,
+};
+
+/// Serialization of the window buffer as a fixed size tuple.
+struct BufferRef<'a, T>(&'a [T]);
+
+impl<T: Serialize> Serialize for BufferRef<'_, T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut tuple = serializer.serialize_tuple(self.0.len())?;
+        for value in self.0 {
+            tuple.serialize_element(value)?;
+        }
+        tuple.end()
+    }
+}
+
+/// Deserialization of the window buffer from a fixed size tuple.
+struct BufferVisitor<T, const WINDOW_SIZE: usize>(PhantomData<T>);
+
+impl<'de, T, const WINDOW_SIZE: usize> Visitor<'de> for BufferVisitor<T, WINDOW_SIZE>
+where
+    T: Deserialize<'de> + Num + Copy,
+{
+    type Value = [T; WINDOW_SIZE];
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "a window buffer of {} items", WINDOW_SIZE)
+    }
+
+    fn visit_seq<S: SeqAccess<'de>>(self, mut seq: S) -> Result<Self::Value, S::Error> {
+        let mut buffer = [T::zero(); WINDOW_SIZE];
+        for (i, item) in buffer.iter_mut().enumerate() {
+            *item = seq
+                .next_element()?
+                .ok_or_else(|| de::Error::invalid_length(i, &self))?;
+        }
+        if seq.next_element::<de::IgnoredAny>()?.is_some() {
+            return Err(de::Error::invalid_length(WINDOW_SIZE + 1, &self));
+        }
+        Ok(buffer)
+    }
+}
+
+fn deserialize_buffer<'de, D, T, const WINDOW_SIZE: usize>(
+    deserializer: D,
+) -> Result<[T; WINDOW_SIZE], D::Error>
+where
+    D: Deserializer<'de>,
+    T: Deserialize<'de> + Num + Copy,
+{
+    deserializer.deserialize_tuple(WINDOW_SIZE, BufferVisitor(PhantomData))
+}
+
+/// The serialized representation of [MovAvg].
+#[derive(Deserialize)]
+#[serde(rename = "MovAvg")]
+#[serde(bound(deserialize = "T: Deserialize<'de> + Num + Copy, A: Deserialize<'de>"))]
+struct Snapshot<T, A, const WINDOW_SIZE: usize> {
+    #[serde(deserialize_with = "deserialize_buffer")]
+    buffer: [T; WINDOW_SIZE],
+    accu: A,
+    comp: A,
+    accu_mode: AccuMode,
+    overflow_policy: OverflowPolicy,
+    rounding: Rounding,
+    nr_since_resync: usize,
+    nr_items: usize,
+    index: usize,
+}
+
+/// Serialize the window buffer and the complete calculation state.
+///
+/// Requires the `serde` cargo feature.
+impl<T, A, const WINDOW_SIZE: usize> Serialize for MovAvg<T, A, WINDOW_SIZE>
+where
+    T: Serialize,
+    A: Serialize,
+{
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let state = &self.state;
+        let mut s = serializer.serialize_struct("MovAvg", 9)?;
+        s.serialize_field("buffer", &BufferRef(&self.buffer[..]))?;
+        s.serialize_field("accu", &state.accu)?;
+        s.serialize_field("comp", &state.comp)?;
+        s.serialize_field("accu_mode", &state.mode)?;
+        s.serialize_field("overflow_policy", &state.policy)?;
+        s.serialize_field("rounding", &state.rounding)?;
+        s.serialize_field("nr_since_resync", &state.nr_since_resync)?;
+        s.serialize_field("nr_items", &state.nr_items)?;
+        s.serialize_field("index", &state.index)?;
+        s.end()
+    }
+}
+
+/// Deserialize and validate the window buffer and the complete calculation state.
+///
+/// Requires the `serde` cargo feature.
+///
+/// Deserialization fails, if the state is inconsistent:
+///
+/// * The number of items is bigger than `WINDOW_SIZE`.
+/// * The index does not match the number of items.
+/// * The accumulator does not match the sum of the items in the window buffer.
+///   With [AccuMode::Exact] float accumulators must match exactly.
+///   With the other float accumulation strategies the accumulator may deviate
+///   by the rounding errors of the summation, that is by about
+///   `N * EPSILON * sum(abs(item))` for `N` items in the window.
+impl<'de, T, A, const WINDOW_SIZE: usize> Deserialize<'de> for MovAvg<T, A, WINDOW_SIZE>
+where
+    T: Deserialize<'de> + Num + NumCast + Copy,
+    A: Deserialize<'de> + Num + NumCast + Copy + MovAvgAccu<T>,
+{
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let snap: Snapshot<T, A, WINDOW_SIZE> = Snapshot::deserialize(deserializer)?;
+        let state = SmaState {
+            accu: snap.accu,
+            comp: snap.comp,
+            mode: snap.accu_mode,
+            policy: snap.overflow_policy,
+            saturated: false,
+            rounding: snap.rounding,
+            nr_since_resync: snap.nr_since_resync,
+            nr_items: snap.nr_items,
+            index: snap.index,
+        };
+        let state = state.validate(&snap.buffer).map_err(de::Error::custom)?;
+        Ok(MovAvg {
+            buffer: snap.buffer,
+            state,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::MovAvgError;
+
+    extern crate std;
+    use std::string::{String, ToString};
+
+    fn to_json<T: Serialize>(value: &T) -> String {
+        serde_json::to_string(value).unwrap()
+    }
+
+    #[test]
+    fn test_roundtrip() {
+        let mut a: MovAvg<i32, i64, 3> = MovAvg::new()
+            .with_accu_mode(AccuMode::Exact)
+            .with_rounding(Rounding::HalfEven);
+        a.feed(10);
+        a.feed(-20);
+        let json = to_json(&a);
+        assert_eq!(
+            json,
+            r#"{"buffer":[10,-20,1],"accu":-10,"comp":0,"accu_mode":"Exact","overflow_policy":"Checked","rounding":"HalfEven","nr_since_resync":0,"nr_items":2,"index":2}"#
+        );
+        let mut b: MovAvg<i32, i64, 3> = serde_json::from_str(&json).unwrap();
+        assert_eq!(b.rounding(), Rounding::HalfEven);
+        for value in [30, 45, -7, 8] {
+            assert_eq!(a.feed(value), b.feed(value));
+        }
+        assert!(a.iter().eq(b.iter()));
+
+        let mut a: MovAvg<f32, f64, 4> = MovAvg::new().with_accu_mode(AccuMode::Resync(10));
+        for i in 0..15 {
+            a.feed(i as f32 * 0.1);
+        }
+        let mut b: MovAvg<f32, f64, 4> = serde_json::from_str(&to_json(&a)).unwrap();
+        assert_eq!(b.sum(), a.sum());
+        for i in 0..15 {
+            assert_eq!(a.feed(i as f32 * 0.3), b.feed(i as f32 * 0.3));
+        }
+    }
+
+    #[test]
+    fn test_invalid() {
+        let ok = r#"{"buffer":[1,2,3],"accu":6,"comp":0,"accu_mode":"Exact","overflow_policy":"Checked","rounding":"Truncate","nr_since_resync":0,"nr_items":3,"index":1}"#;
+        assert!(serde_json::from_str::<MovAvg<u8, u16, 3>>(ok).is_ok());
+
+        let check = |from: &str, to: &str, msg: &str| {
+            let json = ok.replace(from, to);
+            let err = serde_json::from_str::<MovAvg<u8, u16, 3>>(&json).unwrap_err();
+            assert!(err.to_string().contains(msg), "{}", err);
+        };
+        let inconsistent = MovAvgError::InvalidState.to_string();
+        check(r#""accu":6"#, r#""accu":7"#, &inconsistent);
+        check(r#""index":1"#, r#""index":3"#, &inconsistent);
+        check(r#""nr_items":3"#, r#""nr_items":2"#, &inconsistent);
+        check(
+            r#""nr_since_resync":0"#,
+            r#""nr_since_resync":1"#,
+            &inconsistent,
+        );
+        check(
+            r#""nr_items":3"#,
+            r#""nr_items":4"#,
+            &MovAvgError::InvalidNrPopulated.to_string(),
+        );
+        check("[1,2,3]", "[1,2]", "invalid length 2");
+        check("[1,2,3]", "[1,2,3,4]", "invalid length 4");
+        check("[1,2,3]", "[1,2,300]", "invalid value");
+
+        // Partially populated window: The index must be equal to the number of items.
+        let json = ok
+            .replace(r#""accu":6"#, r#""accu":3"#)
+            .replace(r#""nr_items":3"#, r#""nr_items":2"#)
+            .replace(r#""index":1"#, r#""index":2"#);
+        let a: MovAvg<u8, u16, 3> = serde_json::from_str(&json).unwrap();
+        assert!(a.iter().eq([1, 2]));
+    }
+
+    #[test]
+    fn test_float_tolerance() {
+        // The rounding errors of the incremental accumulator are accepted.
+        let mut a: MovAvg<f64, f64, 2> = MovAvg::new().with_accu_mode(AccuMode::Incremental);
+        for value in [0.2, 0.9, 0.9, 0.0] {
+            a.feed(value);
+        }
+        assert_ne!(a.sum(), 0.9);
+        let b: MovAvg<f64, f64, 2> = serde_json::from_str(&to_json(&a)).unwrap();
+        assert_eq!(b.sum(), a.sum());
+
+        let json = |mode: &str, accu: &str| {
+            let json = r#"{"buffer":[1.0,2.0],"accu":ACCU,"comp":0.0,"accu_mode":"MODE","overflow_policy":"Checked","rounding":"Truncate","nr_since_resync":0,"nr_items":2,"index":0}"#;
+            json.replace("MODE", mode).replace("ACCU", accu)
+        };
+        let from_json = |mode, accu| serde_json::from_str::<MovAvg<f64, f64, 2>>(&json(mode, accu));
+        assert!(from_json("Incremental", "3.0000000000000004").is_ok());
+        assert!(from_json("Incremental", "3.000001").is_err());
+        assert!(from_json("Compensated", "3.000001").is_err());
+
+        // The exact accumulator must match exactly.
+        assert!(from_json("Exact", "3.0").is_ok());
+        assert!(from_json("Exact", "3.0000000000000004").is_err());
+    }
+
+    #[test]
+    fn test_saturating() {
+        let mut a: MovAvg<i8, i8, 3> =
+            MovAvg::new().with_overflow_policy(OverflowPolicy::Saturating);
+        for value in [100, 100, -100, 50, 60, 100] {
+            a.feed(value);
+        }
+        assert_eq!(a.get(), 70); // 210 / 3
+        assert_eq!(a.sum(), 127);
+        let mut b: MovAvg<i8, i8, 3> = serde_json::from_str(&to_json(&a)).unwrap();
+        assert_eq!(b.sum(), a.sum());
+        assert_eq!(b.get(), a.get());
+        assert_eq!(b.feed(1), a.feed(1));
+
+        // The wrapped accumulator must match the window buffer.
+        let json = to_json(&a);
+        assert!(json.contains(r#""accu":-95"#)); // 161 - 256
+        let json = json.replace(r#""accu":-95"#, r#""accu":127"#);
+        assert!(serde_json::from_str::<MovAvg<i8, i8, 3>>(&json).is_err());
+    }
+}
+
+// vim: ts=4 sw=4 expandtab
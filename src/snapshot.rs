@@ -0,0 +1,514 @@
+// -*- coding: utf-8 -*-
+//
+// Copyright 2026 Michael Büsch <m@bues.ch>
+//
+// Licensed under the Apache License version 2.0
+// or the MIT license, at your option.
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//
+
+use crate::{
+    error::MovAvgError,
+    sma::{AccuMode, MovAvg, MovAvgAccu, OverflowPolicy, Rounding, SmaState},
+};
+use num_traits::{Num, NumCast};
+
+/// Version of the snapshot layout.
+const SNAPSHOT_VERSION: u8 = 1;
+
+/// Length of the fixed size snapshot header in bytes.
+const HEADER_LEN: usize = 22;
+
+/// Length of the snapshot checksum in bytes.
+const CRC_LEN: usize = 4;
+
+/// Value that can be stored in a binary snapshot.
+///
+/// Values are stored in little endian byte order.
+///
+/// The `movavg` crate implements this trait for all core integers and floats.
+/// Note that the size of `isize` and `usize` depends on the target.
+/// Snapshots with these types can't be restored on targets with a different pointer width.
+///
+/// See [MovAvg::to_bytes].
+pub trait SnapshotValue: Copy {
+    /// The size of the stored value in bytes.
+    const SIZE: usize;
+
+    /// Identifier of the type, which is stored in the snapshot header.
+    ///
+    /// Restoring a snapshot fails, if the identifiers don't match.
+    const TYPE_ID: u8;
+
+    /// Write the value into `out`.
+    ///
+    /// `out` is exactly [Self::SIZE] bytes long.
+    fn write_le(self, out: &mut [u8]);
+
+    /// Read a value from `bytes`.
+    ///
+    /// `bytes` is exactly [Self::SIZE] bytes long.
+    fn read_le(bytes: &[u8]) -> Self;
+}
+
+macro_rules! impl_snapshot_value {
+    ($kind:expr; $($t:ty),*) => {
+        $(
+            impl SnapshotValue for $t {
+                const SIZE: usize = core::mem::size_of::<$t>();
+                const TYPE_ID: u8 = ($kind << 5) | (Self::SIZE as u8);
+
+                fn write_le(self, out: &mut [u8]) {
+                    out.copy_from_slice(&self.to_le_bytes());
+                }
+
+                fn read_le(bytes: &[u8]) -> Self {
+                    let mut buf = [0; core::mem::size_of::<$t>()];
+                    buf.copy_from_slice(bytes);
+                    <$t>::from_le_bytes(buf)
+                }
+            }
+        )*
+    }
+}
+
+impl_snapshot_value!(0; u8, u16, u32, u64, usize);
+impl_snapshot_value!(1; i8, i16, i32, i64, isize);
+impl_snapshot_value!(2; f32, f64);
+
+#[cfg(has_i128)]
+impl_snapshot_value!(0; u128);
+#[cfg(has_i128)]
+impl_snapshot_value!(1; i128);
+
+/// CRC-32 (ISO-HDLC) checksum.
+fn crc32(data: &[u8]) -> u32 {
+    let mut crc = !0_u32;
+    for byte in data {
+        crc ^= *byte as u32;
+        for _ in 0..8 {
+            crc = if crc & 1 != 0 {
+                (crc >> 1) ^ 0xEDB8_8320
+            } else {
+                crc >> 1
+            };
+        }
+    }
+    !crc
+}
+
+/// Sequential writer into a snapshot buffer of sufficient length.
+struct Writer<'a> {
+    out: &'a mut [u8],
+    pos: usize,
+}
+
+impl Writer<'_> {
+    fn bytes(&mut self, bytes: &[u8]) {
+        self.out[self.pos..self.pos + bytes.len()].copy_from_slice(bytes);
+        self.pos += bytes.len();
+    }
+
+    fn u32(&mut self, value: usize) -> Result<(), MovAvgError> {
+        let value = u32::try_from(value).map_err(|_| MovAvgError::InvalidSnapshot)?;
+        self.bytes(&value.to_le_bytes());
+        Ok(())
+    }
+
+    fn value<V: SnapshotValue>(&mut self, value: V) {
+        value.write_le(&mut self.out[self.pos..self.pos + V::SIZE]);
+        self.pos += V::SIZE;
+    }
+}
+
+/// Sequential reader from a snapshot buffer of sufficient length.
+struct Reader<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+}
+
+impl Reader<'_> {
+    fn u32(&mut self) -> Result<usize, MovAvgError> {
+        let value = u32::read_le(&self.bytes[self.pos..self.pos + 4]);
+        self.pos += 4;
+        usize::try_from(value).map_err(|_| MovAvgError::InvalidSnapshot)
+    }
+
+    fn value<V: SnapshotValue>(&mut self) -> V {
+        self.pos += V::SIZE;
+        V::read_le(&self.bytes[self.pos - V::SIZE..self.pos])
+    }
+}
+
+/// Get the length of a snapshot with `nr_items` window items.
+const fn snapshot_len<T: SnapshotValue, A: SnapshotValue>(nr_items: usize) -> usize {
+    HEADER_LEN + (2 * A::SIZE) + (nr_items * T::SIZE) + CRC_LEN
+}
+
+/// Binary snapshots.
+///
+/// Requires the types `T` and `A` to implement [SnapshotValue].
+impl<T, A, const WINDOW_SIZE: usize> MovAvg<T, A, WINDOW_SIZE>
+where
+    T: Num + NumCast + Copy + SnapshotValue,
+    A: Num + NumCast + Copy + MovAvgAccu<T> + SnapshotValue,
+{
+    /// The maximum length of a snapshot in bytes.
+    ///
+    /// That is the length of a snapshot of a fully populated window.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use movavg::MovAvg;
+    ///
+    /// type Avg = MovAvg<i16, i32, 4>;
+    ///
+    /// let mut eeprom = [0_u8; Avg::MAX_SNAPSHOT_LEN];
+    /// assert_eq!(eeprom.len(), 42);
+    /// ```
+    pub const MAX_SNAPSHOT_LEN: usize = snapshot_len::<T, A>(WINDOW_SIZE);
+
+    /// Get the length of the current snapshot in bytes.
+    ///
+    /// Only the populated items of the window are stored.
+    /// Therefore, the length depends on [Self::len].
+    #[inline]
+    pub fn snapshot_len(&self) -> usize {
+        snapshot_len::<T, A>(self.len())
+    }
+
+    /// Store the window items and the complete calculation state
+    /// as binary snapshot into `out`.
+    ///
+    /// On success, returns `Ok(len)` with the number of bytes written to `out`.
+    /// That is [Self::snapshot_len].
+    ///
+    /// Returns `Err`, if `out` is shorter than [Self::snapshot_len].
+    /// ([MovAvgError::BufferTooSmall])
+    ///
+    /// Returns `Err`, if the window size or the resync interval doesn't fit into `u32`.
+    /// ([MovAvgError::InvalidSnapshot])
+    ///
+    /// # Layout
+    ///
+    /// All multi byte values are stored in little endian byte order.
+    /// Only the populated items of the window are stored.
+    ///
+    /// | Offset | Size | Content                                                      |
+    /// |--------|------|--------------------------------------------------------------|
+    /// | 0      | 1    | Layout version (1)                                           |
+    /// | 1      | 1    | Type identifier of `T` ([SnapshotValue::TYPE_ID])            |
+    /// | 2      | 1    | Type identifier of `A` ([SnapshotValue::TYPE_ID])            |
+    /// | 3      | 1    | [AccuMode] (0=Exact, 1=Incremental, 2=Resync, 3=Compensated) |
+    /// | 4      | 1    | [OverflowPolicy] (0=Checked, 1=Saturating, 2=Wrapping)       |
+    /// | 5      | 1    | [Rounding] (0=Truncate, 1=Floor, 2=Ceil, 3=HalfUp, 4=HalfEven) |
+    /// | 6      | 4    | `u32`: Window size                                           |
+    /// | 10     | 4    | `u32`: Number of items in the window (`n`)                   |
+    /// | 14     | 4    | `u32`: Resync interval (0, if not [AccuMode::Resync])        |
+    /// | 18     | 4    | `u32`: Number of feeds since the last resync                 |
+    /// | 22     | `A`  | Accumulator                                                  |
+    /// | ...    | `A`  | Accumulator compensation term                                |
+    /// | ...    | `n * T` | Window items in chronological order (oldest first)        |
+    /// | ...    | 4    | `u32`: CRC-32 (ISO-HDLC) of all preceding bytes              |
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use movavg::MovAvg;
+    ///
+    /// let mut avg: MovAvg<i16, i32, 4> = MovAvg::new();
+    /// avg.feed(10);
+    /// avg.feed(20);
+    ///
+    /// let mut eeprom = [0_u8; MovAvg::<i16, i32, 4>::MAX_SNAPSHOT_LEN];
+    /// let len = avg.to_bytes(&mut eeprom).unwrap();
+    /// assert_eq!(len, 38);
+    ///
+    /// let mut restored: MovAvg<i16, i32, 4> = MovAvg::from_bytes(&eeprom).unwrap();
+    /// assert_eq!(restored.get(), 15);
+    /// assert_eq!(restored.feed(30), avg.feed(30));
+    /// ```
+    pub fn to_bytes(&self, out: &mut [u8]) -> Result<usize, MovAvgError> {
+        let len = self.snapshot_len();
+        if out.len() < len {
+            return Err(MovAvgError::BufferTooSmall);
+        }
+        let state = &self.state;
+        let (mode, interval) = match state.mode {
+            AccuMode::Exact => (0, 0),
+            AccuMode::Incremental => (1, 0),
+            AccuMode::Resync(interval) => (2, interval),
+            AccuMode::Compensated => (3, 0),
+        };
+        let policy = match state.policy {
+            OverflowPolicy::Checked => 0,
+            OverflowPolicy::Saturating => 1,
+            OverflowPolicy::Wrapping => 2,
+        };
+        let rounding = match state.rounding {
+            Rounding::Truncate => 0,
+            Rounding::Floor => 1,
+            Rounding::Ceil => 2,
+            Rounding::HalfUp => 3,
+            Rounding::HalfEven => 4,
+        };
+
+        let mut w = Writer { out, pos: 0 };
+        w.bytes(&[
+            SNAPSHOT_VERSION,
+            T::TYPE_ID,
+            A::TYPE_ID,
+            mode,
+            policy,
+            rounding,
+        ]);
+        w.u32(WINDOW_SIZE)?;
+        w.u32(state.nr_items)?;
+        w.u32(interval)?;
+        w.u32(state.nr_since_resync)?;
+        w.value(state.accu);
+        w.value(state.comp);
+        for value in self.iter() {
+            w.value(value);
+        }
+        let crc = crc32(&w.out[..w.pos]);
+        w.bytes(&crc.to_le_bytes());
+        debug_assert_eq!(w.pos, len);
+        Ok(len)
+    }
+
+    /// Restore a Moving Average from a binary snapshot
+    /// that has been created by [Self::to_bytes].
+    ///
+    /// Trailing bytes after the snapshot in `bytes` are ignored.
+    ///
+    /// Returns `Err`, if the snapshot is truncated, has an unsupported layout version,
+    /// or has been created for different types `T`, `A` or a different `WINDOW_SIZE`.
+    /// ([MovAvgError::InvalidSnapshot])
+    ///
+    /// Returns `Err`, if the checksum does not match.
+    /// ([MovAvgError::SnapshotChecksum])
+    ///
+    /// Returns `Err`, if the restored state is inconsistent.
+    /// For example if the accumulator does not match the window items.
+    /// ([MovAvgError::InvalidState])
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, MovAvgError> {
+        let () = Self::WINDOW_SIZE_CHECK;
+        if bytes.len() < HEADER_LEN || bytes[0] != SNAPSHOT_VERSION {
+            return Err(MovAvgError::InvalidSnapshot);
+        }
+        let mut r = Reader { bytes, pos: 6 };
+        let window_size = r.u32()?;
+        let nr_items = r.u32()?;
+        if nr_items > WINDOW_SIZE {
+            return Err(MovAvgError::InvalidSnapshot);
+        }
+        let len = snapshot_len::<T, A>(nr_items);
+        if bytes.len() < len {
+            return Err(MovAvgError::InvalidSnapshot);
+        }
+        let crc = u32::read_le(&bytes[len - CRC_LEN..len]);
+        if crc != crc32(&bytes[..len - CRC_LEN]) {
+            return Err(MovAvgError::SnapshotChecksum);
+        }
+        if bytes[1] != T::TYPE_ID || bytes[2] != A::TYPE_ID || window_size != WINDOW_SIZE {
+            return Err(MovAvgError::InvalidSnapshot);
+        }
+
+        let interval = r.u32()?;
+        let mode = match (bytes[3], interval) {
+            (0, 0) => AccuMode::Exact,
+            (1, 0) => AccuMode::Incremental,
+            (2, interval) => AccuMode::Resync(interval),
+            (3, 0) => AccuMode::Compensated,
+            _ => return Err(MovAvgError::InvalidSnapshot),
+        };
+        let policy = match bytes[4] {
+            0 => OverflowPolicy::Checked,
+            1 => OverflowPolicy::Saturating,
+            2 => OverflowPolicy::Wrapping,
+            _ => return Err(MovAvgError::InvalidSnapshot),
+        };
+        let rounding = match bytes[5] {
+            0 => Rounding::Truncate,
+            1 => Rounding::Floor,
+            2 => Rounding::Ceil,
+            3 => Rounding::HalfUp,
+            4 => Rounding::HalfEven,
+            _ => return Err(MovAvgError::InvalidSnapshot),
+        };
+        let nr_since_resync = r.u32()?;
+
+        let accu = r.value();
+        let comp = r.value();
+        let mut buffer = [T::one(); WINDOW_SIZE];
+        for item in &mut buffer[..nr_items] {
+            *item = r.value();
+        }
+        debug_assert_eq!(r.pos, len - CRC_LEN);
+
+        let state = SmaState {
+            accu,
+            comp,
+            mode,
+            policy,
+            saturated: false,
+            rounding,
+            nr_since_resync,
+            nr_items,
+            index: nr_items % WINDOW_SIZE,
+        };
+        let state = state.validate(&buffer)?;
+        Ok(MovAvg { buffer, state })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_crc32() {
+        assert_eq!(crc32(b""), 0);
+        assert_eq!(crc32(b"123456789"), 0xCBF4_3926);
+    }
+
+    #[test]
+    fn test_type_id() {
+        assert_eq!(u8::TYPE_ID, 1);
+        assert_eq!(i32::TYPE_ID, 0x24);
+        assert_eq!(f64::TYPE_ID, 0x48);
+        assert_ne!(u32::TYPE_ID, f32::TYPE_ID);
+        assert_ne!(i32::TYPE_ID, f32::TYPE_ID);
+    }
+
+    #[test]
+    fn test_layout() {
+        let mut a: MovAvg<u8, u16, 3> = MovAvg::new()
+            .with_accu_mode(AccuMode::Resync(5))
+            .with_overflow_policy(OverflowPolicy::Wrapping)
+            .with_rounding(Rounding::HalfUp);
+        for value in [10, 20, 30, 40] {
+            a.feed(value);
+        }
+        let mut buf = [0xFF; 40];
+        assert_eq!(a.to_bytes(&mut buf), Ok(33));
+        assert_eq!(
+            buf[..29],
+            [
+                1, // version
+                0x01, 0x02, // types
+                2, 2, 3, // mode, policy, rounding
+                3, 0, 0, 0, // window size
+                3, 0, 0, 0, // nr_items
+                5, 0, 0, 0, // resync interval
+                4, 0, 0, 0, // nr_since_resync
+                90, 0, // accu
+                0, 0, // comp
+                20, 30, 40, // items
+            ]
+        );
+        assert_eq!(buf[29..33], crc32(&buf[..29]).to_le_bytes());
+        assert_eq!(buf[33], 0xFF);
+
+        let mut b: MovAvg<u8, u16, 3> = MovAvg::from_bytes(&buf).unwrap();
+        assert_eq!(b.accu_mode(), AccuMode::Resync(5));
+        assert_eq!(b.overflow_policy(), OverflowPolicy::Wrapping);
+        assert_eq!(b.rounding(), Rounding::HalfUp);
+        assert!(b.iter().eq([20, 30, 40]));
+        for value in [1, 2, 3, 200, 100] {
+            assert_eq!(b.feed(value), a.feed(value));
+        }
+        assert_eq!(b.sum(), a.sum());
+    }
+
+    #[test]
+    fn test_partial() {
+        let mut a: MovAvg<f32, f64, 8> = MovAvg::new().with_accu_mode(AccuMode::Compensated);
+        let mut buf = [0; MovAvg::<f32, f64, 8>::MAX_SNAPSHOT_LEN];
+        assert_eq!(buf.len(), 22 + 16 + 32 + 4);
+        assert_eq!(a.to_bytes(&mut buf), Ok(42));
+        let b: MovAvg<f32, f64, 8> = MovAvg::from_bytes(&buf).unwrap();
+        assert!(b.is_empty());
+
+        a.feed(0.1);
+        a.feed(0.2);
+        assert_eq!(a.to_bytes(&mut buf[..49]), Err(MovAvgError::BufferTooSmall));
+        assert_eq!(a.to_bytes(&mut buf[..50]), Ok(50));
+        let mut b: MovAvg<f32, f64, 8> = MovAvg::from_bytes(&buf).unwrap();
+        assert_eq!(b.sum(), a.sum());
+        assert_eq!(b.feed(0.3), a.feed(0.3));
+    }
+
+    #[test]
+    fn test_invalid() {
+        let mut a: MovAvg<i16, i32, 2> = MovAvg::new();
+        a.feed(-1000);
+        a.feed(3000);
+        let mut buf = [0; 38];
+        assert_eq!(a.to_bytes(&mut buf), Ok(38));
+        assert!(MovAvg::<i16, i32, 2>::from_bytes(&buf).is_ok());
+
+        // Truncated
+        assert_eq!(
+            MovAvg::<i16, i32, 2>::from_bytes(&buf[..37]).unwrap_err(),
+            MovAvgError::InvalidSnapshot
+        );
+        // Bit flips
+        for i in 0..buf.len() {
+            let mut corrupt = buf;
+            corrupt[i] ^= 0x10;
+            assert!(MovAvg::<i16, i32, 2>::from_bytes(&corrupt).is_err());
+        }
+        let mut corrupt = buf;
+        corrupt[22] ^= 1;
+        assert_eq!(
+            MovAvg::<i16, i32, 2>::from_bytes(&corrupt).unwrap_err(),
+            MovAvgError::SnapshotChecksum
+        );
+        // Wrong types or window size
+        assert_eq!(
+            MovAvg::<u16, i32, 2>::from_bytes(&buf).unwrap_err(),
+            MovAvgError::InvalidSnapshot
+        );
+        assert_eq!(
+            MovAvg::<i16, i64, 2>::from_bytes(&buf).unwrap_err(),
+            MovAvgError::InvalidSnapshot
+        );
+        assert_eq!(
+            MovAvg::<i16, i32, 3>::from_bytes(&buf).unwrap_err(),
+            MovAvgError::InvalidSnapshot
+        );
+
+        // Valid checksum, but inconsistent accumulator
+        let mut corrupt = buf;
+        corrupt[22] ^= 1;
+        let crc = crc32(&corrupt[..34]);
+        corrupt[34..].copy_from_slice(&crc.to_le_bytes());
+        assert_eq!(
+            MovAvg::<i16, i32, 2>::from_bytes(&corrupt).unwrap_err(),
+            MovAvgError::InvalidState
+        );
+
+        // Saturated sums are checked, too.
+        let mut a: MovAvg<i8, i8, 2> =
+            MovAvg::new().with_overflow_policy(OverflowPolicy::Saturating);
+        a.feed(100);
+        a.feed(100);
+        let mut buf = [0; 30];
+        assert_eq!(a.to_bytes(&mut buf), Ok(30));
+        let b = MovAvg::<i8, i8, 2>::from_bytes(&buf).unwrap();
+        assert_eq!((b.get(), b.sum()), (100, 127));
+        let mut corrupt = buf;
+        corrupt[22] = 127;
+        let crc = crc32(&corrupt[..26]);
+        corrupt[26..].copy_from_slice(&crc.to_le_bytes());
+        assert_eq!(
+            MovAvg::<i8, i8, 2>::from_bytes(&corrupt).unwrap_err(),
+            MovAvgError::InvalidState
+        );
+    }
+}
+
+// vim: ts=4 sw=4 expandtab
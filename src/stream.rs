@@ -0,0 +1,230 @@
+// -*- coding: utf-8 -*-
+//
+// Copyright 2026 Michael Büsch <m@bues.ch>
+//
+// Licensed under the Apache License version 2.0
+// or the MIT license, at your option.
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//
+
+use crate::sma::{MovAvg, MovAvgAccu};
+use core::{
+    pin::Pin,
+    task::{Context, Poll},
+};
+use futures_core::{FusedStream, Stream};
+use num_traits::{Num, NumCast};
+
+/// Extension trait for streams to calculate the Simple Moving Average of the items.
+///
+/// This trait is implemented for all [Stream]s.
+/// It does not depend on any particular async runtime.
+///
+/// # Examples
+///
+/// ```
+/// use futures::{executor::block_on, stream, StreamExt};
+/// use movavg::MovAvgStreamExt;
+///
+/// let samples = stream::iter([10, 20, 30, 40, 50, 60]);
+/// let avgs: Vec<i32> = block_on(samples.moving_avg::<i32, 3>().collect());
+/// assert_eq!(avgs, [10, 15, 20, 30, 40, 50]);
+///
+/// // Only emit every 2nd average.
+/// let samples = stream::iter([10, 20, 30, 40, 50, 60]);
+/// let avgs: Vec<i32> = block_on(samples.moving_avg::<i32, 3>().decimate(2).collect());
+/// assert_eq!(avgs, [15, 30, 50]);
+/// ```
+pub trait MovAvgStreamExt: Stream + Sized {
+    /// Map each item through [MovAvg::feed] of a new [MovAvg] instance.
+    ///
+    /// The returned stream panics, if [MovAvg::feed] panics.
+    fn moving_avg<A, const WINDOW_SIZE: usize>(self) -> MovAvgStream<Self, A, WINDOW_SIZE>
+    where
+        Self::Item: Num + NumCast + Copy,
+        A: Num + NumCast + Copy + MovAvgAccu<Self::Item>,
+    {
+        self.moving_avg_with(MovAvg::new())
+    }
+
+    /// Map each item through [MovAvg::feed] of the given [MovAvg] instance.
+    ///
+    /// The returned stream panics, if [MovAvg::feed] panics.
+    fn moving_avg_with<A, const WINDOW_SIZE: usize>(
+        self,
+        avg: MovAvg<Self::Item, A, WINDOW_SIZE>,
+    ) -> MovAvgStream<Self, A, WINDOW_SIZE>
+    where
+        Self::Item: Num + NumCast + Copy,
+        A: Num + NumCast + Copy + MovAvgAccu<Self::Item>,
+    {
+        MovAvgStream {
+            stream: self,
+            avg,
+            nth: 1,
+            count: 0,
+        }
+    }
+}
+
+impl<S: Stream> MovAvgStreamExt for S {}
+
+/// Stream that yields the Simple Moving Average of the items of the underlying stream.
+///
+/// See [MovAvgStreamExt::moving_avg].
+///
+/// This stream implements [Stream], if the underlying stream is [Unpin].
+/// Streams that are not [Unpin] can be pinned with `Box::pin` or `core::pin::pin!` first.
+#[derive(Clone, Debug)]
+#[must_use = "streams do nothing unless polled"]
+pub struct MovAvgStream<S: Stream, A, const WINDOW_SIZE: usize> {
+    stream: S,
+    avg: MovAvg<S::Item, A, WINDOW_SIZE>,
+    nth: usize,
+    count: usize,
+}
+
+// The Moving Average state is never pinned.
+impl<S: Stream + Unpin, A, const WINDOW_SIZE: usize> Unpin for MovAvgStream<S, A, WINDOW_SIZE> {}
+
+impl<S, A, const WINDOW_SIZE: usize> MovAvgStream<S, A, WINDOW_SIZE>
+where
+    S: Stream,
+    S::Item: Num + NumCast + Copy,
+    A: Num + NumCast + Copy + MovAvgAccu<S::Item>,
+{
+    /// Only yield every `nth` average.
+    ///
+    /// All items are fed into the Moving Average,
+    /// but only the average after every `nth` fed item is yielded.
+    /// The other averages are dropped.
+    ///
+    /// # Panics
+    ///
+    /// Panics, if `nth` is zero.
+    pub fn decimate(mut self, nth: usize) -> Self {
+        assert!(nth > 0, "The decimation factor must not be zero.");
+        self.nth = nth;
+        self.count = 0;
+        self
+    }
+
+    /// Get the Moving Average state.
+    pub fn movavg(&self) -> &MovAvg<S::Item, A, WINDOW_SIZE> {
+        &self.avg
+    }
+
+    /// Destroy the stream and return the Moving Average state.
+    pub fn into_movavg(self) -> MovAvg<S::Item, A, WINDOW_SIZE> {
+        self.avg
+    }
+}
+
+impl<S, A, const WINDOW_SIZE: usize> Stream for MovAvgStream<S, A, WINDOW_SIZE>
+where
+    S: Stream + Unpin,
+    S::Item: Num + NumCast + Copy,
+    A: Num + NumCast + Copy + MovAvgAccu<S::Item>,
+{
+    type Item = S::Item;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+        loop {
+            match Pin::new(&mut this.stream).poll_next(cx) {
+                Poll::Ready(Some(value)) => {
+                    let avg = this.avg.feed(value);
+                    this.count += 1;
+                    if this.count >= this.nth {
+                        this.count = 0;
+                        return Poll::Ready(Some(avg));
+                    }
+                }
+                Poll::Ready(None) => return Poll::Ready(None),
+                Poll::Pending => return Poll::Pending,
+            }
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (lower, upper) = self.stream.size_hint();
+        (
+            lower.saturating_add(self.count) / self.nth,
+            upper
+                .and_then(|upper| upper.checked_add(self.count))
+                .map(|upper| upper / self.nth),
+        )
+    }
+}
+
+impl<S, A, const WINDOW_SIZE: usize> FusedStream for MovAvgStream<S, A, WINDOW_SIZE>
+where
+    S: FusedStream + Unpin,
+    S::Item: Num + NumCast + Copy,
+    A: Num + NumCast + Copy + MovAvgAccu<S::Item>,
+{
+    fn is_terminated(&self) -> bool {
+        self.stream.is_terminated()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures::{
+        executor::{block_on, block_on_stream},
+        stream, FutureExt, StreamExt,
+    };
+
+    #[test]
+    fn test_moving_avg() {
+        let s = stream::iter([1.0, 2.0, 3.0, 4.0]).moving_avg::<f64, 2>();
+        assert_eq!(s.size_hint(), (4, Some(4)));
+        assert!(block_on_stream(s).eq([1.0, 1.5, 2.5, 3.5]));
+
+        let avg: MovAvg<u8, u16, 3> = MovAvg::new_init([10, 20, 30], 2);
+        let mut s = stream::iter([3_u8, 6]).fuse().moving_avg_with(avg);
+        assert_eq!(block_on(s.next()), Some(11)); // 33 / 3
+        assert_eq!(block_on(s.next()), Some(9)); // 29 / 3
+        assert_eq!(block_on(s.next()), None);
+        assert!(s.is_terminated());
+        assert_eq!(s.into_movavg().get(), 9);
+    }
+
+    #[test]
+    fn test_decimate() {
+        let s = stream::iter(1_u32..=10).moving_avg::<u32, 2>().decimate(3);
+        assert_eq!(s.size_hint(), (3, Some(3)));
+        assert!(block_on_stream(s).eq([2, 5, 8]));
+
+        let mut s = stream::iter(1_u32..=10).moving_avg::<u32, 2>().decimate(4);
+        assert_eq!(block_on(s.next()), Some(3));
+        assert_eq!(s.movavg().len(), 2);
+        assert_eq!(s.size_hint(), (1, Some(1)));
+        assert_eq!(block_on(s.next()), Some(7));
+        assert_eq!(block_on(s.next()), None);
+
+        let s = stream::iter(1_u32..=3).moving_avg::<u32, 2>().decimate(1);
+        assert!(block_on_stream(s).eq([1, 1, 2]));
+    }
+
+    #[test]
+    fn test_pending() {
+        let (tx, rx) = futures::channel::mpsc::unbounded::<i32>();
+        let mut s = rx.moving_avg::<i32, 2>().decimate(2);
+        tx.unbounded_send(10).unwrap();
+        assert_eq!(s.next().now_or_never(), None); // Pending
+        tx.unbounded_send(20).unwrap();
+        assert_eq!(s.next().now_or_never(), Some(Some(15)));
+        drop(tx);
+        assert_eq!(block_on(s.next()), None);
+    }
+
+    #[test]
+    #[should_panic(expected = "The decimation factor must not be zero.")]
+    fn test_decimate_zero() {
+        let _ = stream::iter([1_u8]).moving_avg::<u8, 2>().decimate(0);
+    }
+}
+
+// vim: ts=4 sw=4 expandtab
@@ -0,0 +1,601 @@
+// -*- coding: utf-8 -*-
+//
+// Copyright 2026 Michael Büsch <m@bues.ch>
+//
+// Licensed under the Apache License version 2.0
+// or the MIT license, at your option.
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//
+
+use crate::error::MovAvgError;
+#[cfg(any(feature = "std", feature = "libm"))]
+use num_traits::Float;
+use num_traits::{Num, NumCast};
+
+/// Internal variance accumulator calculation trait for integers and floats.
+///
+/// This usually does *not* have to be implemented by the library user.
+/// The `movavg` crate implements this trait for all core integers and floats.
+///
+/// `Self` is the accumulator type `A`.
+///
+/// `T` is the input value type.
+///
+/// The state consists of two accumulators.
+/// Integers store the plain sum and the sum of squares.
+/// Floats store the mean and the sum of squared deviations from the mean (Welford).
+pub trait MovVarAccu<T>: Copy {
+    /// Add `input_value` to the state.
+    ///
+    /// `nr_items` is the number of items in the window before adding `input_value`.
+    /// If `first_value` is `Some`, then it is removed from the window at the same time
+    /// and the number of items stays the same.
+    fn recalc_var_accu(
+        accu: [Self; 2],
+        nr_items: usize,
+        first_value: Option<Self>,
+        input_value: Self,
+    ) -> Result<[Self; 2], MovAvgError>;
+
+    /// Calculate the mean of `nr_items` items.
+    fn var_mean(accu: [Self; 2], nr_items: usize) -> Result<Self, MovAvgError>;
+
+    /// Calculate the variance of `nr_items` items
+    /// with `ddof` delta degrees of freedom.
+    ///
+    /// `nr_items` must be bigger than `ddof`.
+    fn var_variance(accu: [Self; 2], nr_items: usize, ddof: usize) -> Result<Self, MovAvgError>;
+
+    /// Calculate the square root of the non-negative `self`.
+    ///
+    /// Integers return the square root rounded down.
+    fn var_sqrt(self) -> Self;
+}
+
+macro_rules! impl_int_var_accu {
+    ($($t:ty),*) => {
+        $(
+            impl<T> MovVarAccu<T> for $t {
+                #[inline]
+                fn recalc_var_accu(accu: [Self; 2],
+                                   _nr_items: usize,
+                                   first_value: Option<Self>,
+                                   input_value: Self) -> Result<[Self; 2], MovAvgError> {
+                    let [mut sum, mut sum_sq] = accu;
+                    if let Some(first_value) = first_value {
+                        // The square of first_value can't overflow,
+                        // because it has been added before.
+                        sum -= first_value;
+                        sum_sq -= first_value * first_value;
+                    }
+                    let input_sq = input_value.checked_mul(input_value)
+                        .ok_or(MovAvgError::AccuOverflow)?;
+                    Ok([
+                        sum.checked_add(input_value).ok_or(MovAvgError::AccuOverflow)?,
+                        sum_sq.checked_add(input_sq).ok_or(MovAvgError::AccuOverflow)?,
+                    ])
+                }
+
+                #[inline]
+                fn var_mean(accu: [Self; 2], nr_items: usize) -> Result<Self, MovAvgError> {
+                    let n = <$t as NumCast>::from(nr_items).ok_or(MovAvgError::CastCount)?;
+                    Ok(accu[0] / n)
+                }
+
+                #[inline]
+                fn var_variance(accu: [Self; 2],
+                                nr_items: usize,
+                                ddof: usize) -> Result<Self, MovAvgError> {
+                    let [sum, sum_sq] = accu;
+                    let n = <$t as NumCast>::from(nr_items).ok_or(MovAvgError::CastCount)?;
+                    let d = <$t as NumCast>::from(nr_items - ddof).ok_or(MovAvgError::CastCount)?;
+                    // var = (n * sum_sq - sum^2) / (n * (n - ddof))
+                    let sum2 = sum.checked_mul(sum).ok_or(MovAvgError::AccuOverflow)?;
+                    let numerator = n.checked_mul(sum_sq)
+                        .and_then(|v| v.checked_sub(sum2))
+                        .ok_or(MovAvgError::AccuOverflow)?;
+                    let denominator = n.checked_mul(d).ok_or(MovAvgError::AccuOverflow)?;
+                    Ok(numerator / denominator)
+                }
+
+                #[inline]
+                fn var_sqrt(self) -> Self {
+                    if self < 2 {
+                        return self;
+                    }
+                    // Newton's method: x = (x + self / x) / 2
+                    // The estimate x decreases until it reaches floor(sqrt(self)).
+                    // The sum is calculated as q + (x - q) / 2 to avoid overflow.
+                    let mut x = self / 2 + 1;
+                    loop {
+                        let q = self / x;
+                        if q >= x {
+                            return x;
+                        }
+                        x = q + (x - q) / 2;
+                    }
+                }
+            }
+        )*
+    }
+}
+
+#[cfg(any(feature = "std", feature = "libm"))]
+macro_rules! impl_float_var_accu {
+    ($($t:ty),*) => {
+        $(
+            impl<T> MovVarAccu<T> for $t {
+                #[inline]
+                fn recalc_var_accu(accu: [Self; 2],
+                                   nr_items: usize,
+                                   first_value: Option<Self>,
+                                   input_value: Self) -> Result<[Self; 2], MovAvgError> {
+                    let [mean, m2] = accu;
+                    let (new_mean, new_m2) = if let Some(first_value) = first_value {
+                        // Replace first_value by input_value.
+                        let n = nr_items as $t;
+                        let diff = input_value - first_value;
+                        let new_mean = mean + diff / n;
+                        (new_mean,
+                         m2 + diff * ((input_value - new_mean) + (first_value - mean)))
+                    } else {
+                        // Add input_value.
+                        let n = (nr_items + 1) as $t;
+                        let delta = input_value - mean;
+                        let new_mean = mean + delta / n;
+                        (new_mean, m2 + delta * (input_value - new_mean))
+                    };
+                    // Rounding errors must not make the sum of squares negative.
+                    Ok([new_mean, new_m2.max(0.0)])
+                }
+
+                #[inline]
+                fn var_mean(accu: [Self; 2], _nr_items: usize) -> Result<Self, MovAvgError> {
+                    Ok(accu[0])
+                }
+
+                #[inline]
+                fn var_variance(accu: [Self; 2],
+                                nr_items: usize,
+                                ddof: usize) -> Result<Self, MovAvgError> {
+                    Ok(accu[1] / (nr_items - ddof) as $t)
+                }
+
+                #[inline]
+                fn var_sqrt(self) -> Self {
+                    Float::sqrt(self)
+                }
+            }
+        )*
+    }
+}
+
+impl_int_var_accu!(i8, i16, i32, i64, isize, u8, u16, u32, u64, usize);
+
+#[cfg(has_i128)]
+impl_int_var_accu!(i128, u128);
+
+#[cfg(any(feature = "std", feature = "libm"))]
+impl_float_var_accu!(f32, f64);
+
+/// Moving variance and standard deviation
+///
+/// Calculates the mean, the variance and the standard deviation
+/// of a sliding window.
+///
+/// For integer accumulators the plain sum and the sum of squares are maintained.
+/// The variance is calculated from these with integer arithmetic
+/// and the result is rounded towards zero.
+///
+/// For float accumulators a numerically stable Welford-style algorithm is used.
+/// Float accumulators require the `std` or the `libm` cargo feature.
+///
+/// # Examples
+///
+/// ```
+/// use movavg::MovVar;
+///
+/// let mut var: MovVar<i32, i64, 8> = MovVar::new(); // window size = 8
+/// for value in [2, 4, 4, 4, 5, 5, 7, 9] {
+///     var.feed(value);
+/// }
+/// assert_eq!(var.get(), 5);
+/// assert_eq!(var.variance(), 4);
+/// assert_eq!(var.std_dev(), 2);
+/// assert_eq!(var.sample_variance(), 4); // 32 / 7
+/// ```
+///
+/// ```
+/// # #[cfg(any(feature = "std", feature = "libm"))]
+/// # {
+/// use movavg::MovVar;
+///
+/// let mut var: MovVar<f64, f64, 3> = MovVar::new(); // window size = 3
+/// var.feed(1.0);
+/// var.feed(2.0);
+/// var.feed(3.0);
+/// assert_eq!(var.feed(4.0), 3.0); // mean
+/// assert!((var.sample_variance() - 1.0).abs() < 1e-9);
+/// # }
+/// ```
+///
+/// # Type Generics
+///
+/// `struct MovVar<T, A, WINDOW_SIZE>`
+///
+/// * `T` - The type of the `feed()` input value.
+/// * `A` - The type of the internal accumulator.
+///   For integers this type must be big enough to hold `WINDOW_SIZE` times the
+///   square of the input values.
+///   The variance calculation needs another factor of `WINDOW_SIZE`.
+/// * `WINDOW_SIZE` - The size of the sliding window.
+///   In number of fed elements.
+#[derive(Clone, Debug)]
+pub struct MovVar<T, A, const WINDOW_SIZE: usize> {
+    buffer: [T; WINDOW_SIZE],
+    accu: [A; 2],
+    nr_items: usize,
+    index: usize,
+}
+
+impl<T, A, const WINDOW_SIZE: usize> MovVar<T, A, WINDOW_SIZE>
+where
+    T: Num + NumCast + Copy,
+    A: Num + NumCast + Copy + MovVarAccu<T>,
+{
+    /// Compile time check of the `WINDOW_SIZE` generic.
+    const WINDOW_SIZE_CHECK: () = assert!(WINDOW_SIZE > 0, "WINDOW_SIZE must not be zero.");
+
+    /// Construct a new moving variance.
+    ///
+    /// The internal accumulators default to zero.
+    pub fn new() -> MovVar<T, A, WINDOW_SIZE> {
+        let () = Self::WINDOW_SIZE_CHECK;
+        MovVar {
+            buffer: [T::zero(); WINDOW_SIZE],
+            accu: [A::zero(); 2],
+            nr_items: 0,
+            index: 0,
+        }
+    }
+
+    /// Reset the moving variance.
+    ///
+    /// This resets the accumulators and the number of accumulated items to 0,
+    /// as if this instance was re-created with [MovVar::new].
+    pub fn reset(&mut self) {
+        self.accu = [A::zero(); 2];
+        self.nr_items = 0;
+        self.index = 0;
+    }
+
+    /// Get the current length of the window.
+    ///
+    /// This length is in the range of `0..WINDOW_SIZE`.
+    /// If the length is less than `WINDOW_SIZE`, then the window
+    /// has not been fully populated, yet.
+    ///
+    /// See [Self::window_size] to get the nominal maximum length of the window.
+    #[inline]
+    pub const fn len(&self) -> usize {
+        self.nr_items
+    }
+
+    /// Check if no items have been fed, yet.
+    ///
+    /// This returns true, if [Self::len] `==0`.
+    ///
+    /// This returns false, if [Self::len] `!=0`.
+    #[inline]
+    pub const fn is_empty(&self) -> bool {
+        self.nr_items == 0
+    }
+
+    /// Get the nominal size of the window.
+    ///
+    /// This is always equal to `WINDOW_SIZE`.
+    ///
+    /// See [Self::len] to get the actual current length of the window.
+    #[inline]
+    pub const fn window_size(&self) -> usize {
+        WINDOW_SIZE
+    }
+
+    /// Try to feed a new value into the window and return the new mean.
+    ///
+    /// * `value` - The new value to feed into the window.
+    ///
+    /// On success, returns `Ok(T)` with the new mean.
+    ///
+    /// Returns `Err`, if the internal accumulator overflows, or if any value conversion fails.
+    /// Value conversion does not fail, if the types are big enough to hold the values.
+    pub fn try_feed(&mut self, value: T) -> Result<T, MovAvgError> {
+        let full = self.nr_items >= WINDOW_SIZE;
+
+        // Get the first element from the moving window state.
+        let first_value = if full {
+            Some(A::from(self.buffer[self.index]).ok_or(MovAvgError::CastInput)?)
+        } else {
+            None
+        };
+
+        let a_value = A::from(value).ok_or(MovAvgError::CastInput)?;
+
+        let new_accu = A::recalc_var_accu(self.accu, self.nr_items, first_value, a_value)?;
+        let new_nr_items = if full {
+            self.nr_items // Already fully populated.
+        } else {
+            self.nr_items + 1
+        };
+        let mean = T::from(A::var_mean(new_accu, new_nr_items)?).ok_or(MovAvgError::CastResult)?;
+
+        // Update the state.
+        self.buffer[self.index] = value;
+        self.accu = new_accu;
+        self.nr_items = new_nr_items;
+        self.index = (self.index + 1) % WINDOW_SIZE;
+
+        Ok(mean)
+    }
+
+    /// Feed a new value into the window and return the new mean.
+    ///
+    /// * `value` - The new value to feed into the window.
+    ///
+    /// # Panics
+    ///
+    /// Panics, if the internal accumulator overflows, or if any value conversion fails.
+    /// Value conversion does not fail, if the types are big enough to hold the values.
+    pub fn feed(&mut self, value: T) -> T {
+        match self.try_feed(value) {
+            Ok(mean) => mean,
+            Err(e) => panic!("MovVar calculation failed: {}", e),
+        }
+    }
+
+    /// Try to get the current mean of the window.
+    ///
+    /// Returns `Err`, if the internal state is empty.
+    ///
+    /// Returns `Err`, if any value conversion fails.
+    pub fn try_get(&self) -> Result<T, MovAvgError> {
+        if self.nr_items == 0 {
+            Err(MovAvgError::Empty)
+        } else {
+            T::from(A::var_mean(self.accu, self.nr_items)?).ok_or(MovAvgError::CastResult)
+        }
+    }
+
+    /// Get the current mean of the window.
+    ///
+    /// # Panics
+    ///
+    /// Panics, if the internal state is empty, or if any value conversion fails.
+    pub fn get(&self) -> T {
+        match self.try_get() {
+            Ok(mean) => mean,
+            Err(e) => panic!("MovVar calculation failed: {}", e),
+        }
+    }
+
+    /// Calculate the variance with `ddof` delta degrees of freedom.
+    fn try_variance_ddof(&self, ddof: usize) -> Result<A, MovAvgError> {
+        if self.nr_items == 0 {
+            Err(MovAvgError::Empty)
+        } else if self.nr_items <= ddof {
+            Err(MovAvgError::TooFewItems)
+        } else {
+            A::var_variance(self.accu, self.nr_items, ddof)
+        }
+    }
+
+    /// Try to get the population variance of the window.
+    ///
+    /// This is the sum of squared deviations from the mean divided by [Self::len].
+    ///
+    /// Returns `Err`, if the internal state is empty, or if the accumulator overflows.
+    pub fn try_variance(&self) -> Result<A, MovAvgError> {
+        self.try_variance_ddof(0)
+    }
+
+    /// Get the population variance of the window.
+    ///
+    /// # Panics
+    ///
+    /// Panics, if the internal state is empty, or if the accumulator overflows.
+    pub fn variance(&self) -> A {
+        match self.try_variance() {
+            Ok(var) => var,
+            Err(e) => panic!("MovVar calculation failed: {}", e),
+        }
+    }
+
+    /// Try to get the sample variance of the window.
+    ///
+    /// This is the sum of squared deviations from the mean divided by [Self::len] `- 1`.
+    ///
+    /// Returns `Err`, if less than two items are in the window,
+    /// or if the accumulator overflows.
+    pub fn try_sample_variance(&self) -> Result<A, MovAvgError> {
+        self.try_variance_ddof(1)
+    }
+
+    /// Get the sample variance of the window.
+    ///
+    /// # Panics
+    ///
+    /// Panics, if less than two items are in the window,
+    /// or if the accumulator overflows.
+    pub fn sample_variance(&self) -> A {
+        match self.try_sample_variance() {
+            Ok(var) => var,
+            Err(e) => panic!("MovVar calculation failed: {}", e),
+        }
+    }
+
+    /// Try to get the population standard deviation of the window.
+    ///
+    /// This is the square root of [Self::variance].
+    ///
+    /// Returns `Err`, if the internal state is empty, or if the accumulator overflows.
+    pub fn try_std_dev(&self) -> Result<A, MovAvgError> {
+        Ok(self.try_variance()?.var_sqrt())
+    }
+
+    /// Get the population standard deviation of the window.
+    ///
+    /// # Panics
+    ///
+    /// Panics, if the internal state is empty, or if the accumulator overflows.
+    pub fn std_dev(&self) -> A {
+        match self.try_std_dev() {
+            Ok(std_dev) => std_dev,
+            Err(e) => panic!("MovVar calculation failed: {}", e),
+        }
+    }
+
+    /// Try to get the sample standard deviation of the window.
+    ///
+    /// This is the square root of [Self::sample_variance].
+    ///
+    /// Returns `Err`, if less than two items are in the window,
+    /// or if the accumulator overflows.
+    pub fn try_sample_std_dev(&self) -> Result<A, MovAvgError> {
+        Ok(self.try_sample_variance()?.var_sqrt())
+    }
+
+    /// Get the sample standard deviation of the window.
+    ///
+    /// # Panics
+    ///
+    /// Panics, if less than two items are in the window,
+    /// or if the accumulator overflows.
+    pub fn sample_std_dev(&self) -> A {
+        match self.try_sample_std_dev() {
+            Ok(std_dev) => std_dev,
+            Err(e) => panic!("MovVar calculation failed: {}", e),
+        }
+    }
+}
+
+impl<A, T, const WINDOW_SIZE: usize> Default for MovVar<T, A, WINDOW_SIZE>
+where
+    T: Num + NumCast + Copy,
+    A: Num + NumCast + Copy + MovVarAccu<T>,
+{
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Reference implementation of the variance.
+    #[cfg(any(feature = "std", feature = "libm"))]
+    fn variance(window: &[f64], ddof: usize) -> f64 {
+        let n = window.len() as f64;
+        let mean = window.iter().sum::<f64>() / n;
+        window.iter().map(|v| (v - mean) * (v - mean)).sum::<f64>() / (n - ddof as f64)
+    }
+
+    #[test]
+    fn test_int_sqrt() {
+        for i in 0_u32..10_000 {
+            let r = MovVarAccu::<u32>::var_sqrt(i);
+            assert!(r * r <= i && (r + 1) * (r + 1) > i);
+        }
+        assert_eq!(MovVarAccu::<u8>::var_sqrt(u8::MAX), 15);
+        assert_eq!(MovVarAccu::<i64>::var_sqrt(i64::MAX), 3_037_000_499);
+        assert_eq!(MovVarAccu::<u64>::var_sqrt(u64::MAX), 4_294_967_295);
+    }
+
+    #[test]
+    fn test_i32() {
+        let mut a: MovVar<i32, i64, 4> = MovVar::new();
+        assert!(a.try_variance().is_err());
+        assert_eq!(a.feed(10), 10);
+        assert_eq!(a.variance(), 0);
+        assert_eq!(a.try_sample_variance(), Err(MovAvgError::TooFewItems));
+        assert_eq!(a.feed(20), 15);
+        assert_eq!(a.variance(), 25);
+        assert_eq!(a.sample_variance(), 50);
+        assert_eq!(a.feed(-30), 0);
+        assert_eq!(a.variance(), 466); // 466.67
+        assert_eq!(a.feed(40), 10);
+        assert_eq!(a.variance(), 650);
+        assert_eq!(a.std_dev(), 25); // 25.50
+        assert_eq!(a.sample_variance(), 866); // 866.67
+        assert_eq!(a.sample_std_dev(), 29); // 29.44
+        assert_eq!(a.feed(50), 20);
+        assert_eq!(a.variance(), 950); // [20, -30, 40, 50]
+        assert_eq!(a.len(), 4);
+    }
+
+    #[test]
+    fn test_u16() {
+        let mut a: MovVar<u16, u64, 3> = MovVar::new();
+        assert_eq!(a.feed(u16::MAX), u16::MAX);
+        assert_eq!(a.feed(0), u16::MAX / 2);
+        assert_eq!(a.feed(u16::MAX), 43690);
+        assert_eq!(a.feed(u16::MAX), 43690);
+        assert_eq!(a.feed(u16::MAX), u16::MAX);
+        assert_eq!(a.variance(), 0);
+    }
+
+    #[test]
+    #[cfg(any(feature = "std", feature = "libm"))]
+    fn test_f64() {
+        let mut a: MovVar<f64, f64, 5> = MovVar::new();
+        let mut window = [0.0; 5];
+        let mut value = 1.0;
+        for i in 0..1000 {
+            value = (value * 7919.0 + 13.0) % 10007.0 - 5000.0;
+            window.copy_within(1.., 0);
+            window[4] = value;
+            a.feed(value);
+            let n = (i + 1).min(5);
+            let e = 1e-6 * variance(&window[5 - n..], 0).max(1.0);
+            assert!((a.variance() - variance(&window[5 - n..], 0)).abs() < e);
+            if n > 1 {
+                assert!((a.sample_variance() - variance(&window[5 - n..], 1)).abs() < e);
+            }
+        }
+    }
+
+    #[test]
+    #[cfg(any(feature = "std", feature = "libm"))]
+    fn test_f32_constant() {
+        let mut a: MovVar<f32, f32, 3> = MovVar::new();
+        for _ in 0..100 {
+            a.feed(0.1);
+        }
+        assert!(a.variance() >= 0.0);
+        assert!(a.std_dev() < 1e-3);
+    }
+
+    #[test]
+    fn test_accu_overflow() {
+        let mut a: MovVar<i16, i16, 3> = MovVar::new();
+        assert_eq!(a.feed(100), 100);
+        assert_eq!(a.try_feed(200), Err(MovAvgError::AccuOverflow));
+        assert_eq!(a.len(), 1);
+        assert_eq!(a.get(), 100);
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut a: MovVar<i32, i32, 3> = MovVar::new();
+        assert_eq!(a.feed(10), 10);
+        assert_eq!(a.feed(20), 15);
+        a.reset();
+        assert!(a.is_empty());
+        assert_eq!(a.try_get(), Err(MovAvgError::Empty));
+        assert_eq!(a.feed(40), 40);
+        assert_eq!(a.variance(), 0);
+    }
+}
+
+// vim: ts=4 sw=4 expandtab
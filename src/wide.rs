@@ -0,0 +1,172 @@
+// -*- coding: utf-8 -*-
+//
+// Copyright 2026 Michael Büsch <m@bues.ch>
+//
+// Licensed under the Apache License version 2.0
+// or the MIT license, at your option.
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//
+
+use crate::sma::MovAvg;
+
+/// Mapping of an input value type to a wide default accumulator type.
+///
+/// The `movavg` crate implements this trait for all core integers and floats:
+///
+/// | Input type `Self`  | Accumulator type [WideAccu::Accu] |
+/// |--------------------|-----------------------------------|
+/// | i8, i16            | i32, i64                          |
+/// | i32, i64, isize    | i64, i128, i128                   |
+/// | i128               | i128                              |
+/// | u8, u16            | u32, u64                          |
+/// | u32, u64, usize    | u64, u128, u128                   |
+/// | u128               | u128                              |
+/// | f32, f64           | f64, f64                          |
+///
+/// See [WideMovAvg] and [MovAvg::new_wide].
+pub trait WideAccu {
+    /// The wide default accumulator type.
+    type Accu;
+
+    /// The maximum window size for which the accumulator can never overflow.
+    ///
+    /// That is the maximum `WINDOW_SIZE` for which `WINDOW_SIZE * Self::MAX`
+    /// (and `WINDOW_SIZE * Self::MIN`) fits into the accumulator type.
+    /// This is `usize::MAX` for floats.
+    const MAX_WINDOW_SIZE: usize;
+}
+
+/// Calculate [WideAccu::MAX_WINDOW_SIZE] from the quotient of the type limits.
+const fn max_window_size(quotient: u128) -> usize {
+    if quotient > usize::MAX as u128 {
+        usize::MAX
+    } else {
+        quotient as usize
+    }
+}
+
+macro_rules! impl_wide_accu_signed {
+    ($($t:ty => $a:ty),*) => {
+        $(
+            impl WideAccu for $t {
+                type Accu = $a;
+
+                const MAX_WINDOW_SIZE: usize = {
+                    let max = (<$a>::MAX / (<$t>::MAX as $a)) as u128;
+                    let min = (<$a>::MIN / (<$t>::MIN as $a)) as u128;
+                    max_window_size(if max < min { max } else { min })
+                };
+            }
+        )*
+    }
+}
+
+macro_rules! impl_wide_accu_unsigned {
+    ($($t:ty => $a:ty),*) => {
+        $(
+            impl WideAccu for $t {
+                type Accu = $a;
+
+                const MAX_WINDOW_SIZE: usize =
+                    max_window_size((<$a>::MAX / (<$t>::MAX as $a)) as u128);
+            }
+        )*
+    }
+}
+
+macro_rules! impl_wide_accu_float {
+    ($($t:ty => $a:ty),*) => {
+        $(
+            impl WideAccu for $t {
+                type Accu = $a;
+
+                const MAX_WINDOW_SIZE: usize = usize::MAX;
+            }
+        )*
+    }
+}
+
+impl_wide_accu_signed!(i8 => i32, i16 => i64, i32 => i64);
+impl_wide_accu_unsigned!(u8 => u32, u16 => u64, u32 => u64);
+
+#[cfg(has_i128)]
+impl_wide_accu_signed!(i64 => i128, isize => i128, i128 => i128);
+#[cfg(has_i128)]
+impl_wide_accu_unsigned!(u64 => u128, usize => u128, u128 => u128);
+
+impl_wide_accu_float!(f32 => f64, f64 => f64);
+
+/// Simple Moving Average (SMA) with a wide default accumulator.
+///
+/// This is a [MovAvg] with the accumulator type selected by [WideAccu].
+///
+/// Construct it with [MovAvg::new_wide] to check at compile time
+/// that the accumulator can never overflow.
+/// This is the only constructor with this check.
+///
+/// `WideMovAvg` is a type alias of [MovAvg].
+/// Therefore, [MovAvg::new], [MovAvg::new_init] and `Default` can be used, too,
+/// but they do *not* check the accumulator capacity.
+/// An accumulator overflow is then reported at runtime, as with any other [MovAvg].
+///
+/// # Examples
+///
+/// ```
+/// use movavg::WideMovAvg;
+///
+/// let mut avg: WideMovAvg<i8, 3> = WideMovAvg::new_wide(); // i32 accumulator
+/// assert_eq!(avg.feed(100), 100);
+/// assert_eq!(avg.feed(100), 100);
+/// ```
+///
+/// A window size that could overflow the accumulator is rejected at compile time:
+///
+/// ```compile_fail
+/// use movavg::WideMovAvg;
+///
+/// let mut avg: WideMovAvg<i128, 2> = WideMovAvg::new_wide();
+/// ```
+pub type WideMovAvg<T, const WINDOW_SIZE: usize> = MovAvg<T, <T as WideAccu>::Accu, WINDOW_SIZE>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_max_window_size() {
+        assert_eq!(<i8 as WideAccu>::MAX_WINDOW_SIZE, 1 << 24);
+        assert_eq!(<u8 as WideAccu>::MAX_WINDOW_SIZE, (u32::MAX / 255) as usize);
+        assert_eq!(<i16 as WideAccu>::MAX_WINDOW_SIZE, max_window_size(1 << 48));
+        assert_eq!(
+            <u32 as WideAccu>::MAX_WINDOW_SIZE,
+            max_window_size(u32::MAX as u128 + 2)
+        );
+        assert_eq!(<f32 as WideAccu>::MAX_WINDOW_SIZE, usize::MAX);
+    }
+
+    #[cfg(has_i128)]
+    #[test]
+    fn test_max_window_size_i128() {
+        assert_eq!(<i64 as WideAccu>::MAX_WINDOW_SIZE, usize::MAX);
+        assert_eq!(<u64 as WideAccu>::MAX_WINDOW_SIZE, usize::MAX);
+        assert_eq!(<i128 as WideAccu>::MAX_WINDOW_SIZE, 1);
+        assert_eq!(<u128 as WideAccu>::MAX_WINDOW_SIZE, 1);
+    }
+
+    #[test]
+    fn test_wide_movavg() {
+        let mut avg: WideMovAvg<u8, 1024> = MovAvg::new_wide();
+        for _ in 0..2048 {
+            assert_eq!(avg.feed(u8::MAX), u8::MAX);
+        }
+        let mut avg: WideMovAvg<i32, 3> = WideMovAvg::new_wide();
+        assert_eq!(avg.feed(i32::MIN), i32::MIN);
+        assert_eq!(avg.feed(i32::MIN), i32::MIN);
+        assert_eq!(avg.feed(i32::MAX), -715_827_883);
+        let mut avg: WideMovAvg<f32, 2> = WideMovAvg::new_wide();
+        avg.feed(f32::MAX);
+        assert_eq!(avg.feed(f32::MAX), f32::MAX);
+    }
+}
+
+// vim: ts=4 sw=4 expandtab
@@ -0,0 +1,830 @@
+// -*- coding: utf-8 -*-
+//
+// Copyright 2026 Michael Büsch <m@bues.ch>
+//
+// Licensed under the Apache License version 2.0
+// or the MIT license, at your option.
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//
+
+use crate::{
+    error::MovAvgError,
+    sma::{neumaier_add, AccuMode},
+};
+use num_traits::{Num, NumCast};
+
+/// Initialize the plain sum and the weighted sum from scratch.
+///
+/// `window_buffers` contains the window items in chronological order (oldest first).
+/// The oldest item gets the weight 1.
+#[inline]
+fn initialize_wma_accu<T, A>(window_buffers: [&[T]; 2]) -> Result<(A, A), MovAvgError>
+where
+    T: Num + NumCast + Copy,
+    A: Num + NumCast + Copy,
+{
+    let mut sum = A::zero();
+    let mut weighted_sum = A::zero();
+    let mut weight = A::zero();
+    for value in window_buffers.iter().flat_map(|b| b.iter()) {
+        if let Some(value) = A::from(*value) {
+            weight = weight + A::one();
+            sum = sum + value;
+            weighted_sum = weighted_sum + weight * value;
+        } else {
+            return Err(MovAvgError::CastInput);
+        }
+    }
+    Ok((sum, weighted_sum))
+}
+
+/// Internal weighted accumulator calculation trait for integers and floats.
+///
+/// This usually does *not* have to be implemented by the library user.
+/// The `movavg` crate implements this trait for all core integers and floats.
+///
+/// `Self` is the accumulator type `A`.
+///
+/// `T` is the WMA input value type.
+pub trait WmaAccu<T>: Copy {
+    /// Calculate the new plain sum and the new weighted sum incrementally.
+    ///
+    /// `self` is the plain sum of the window.
+    /// If `first_value` is `Some`, then the window is full.
+    /// The first value is removed from the window
+    /// and all remaining weights are decremented by one.
+    /// `input_value` is added with the weight `weight`.
+    ///
+    /// `window_buffers` contains all items of the window in chronological order,
+    /// including `input_value`.
+    fn recalc_wma_accu(
+        self,
+        weighted_sum: Self,
+        first_value: Option<Self>,
+        input_value: Self,
+        weight: Self,
+        window_buffers: [&[T]; 2],
+    ) -> Result<(Self, Self), MovAvgError>;
+
+    /// Calculate the new plain sum and the new weighted sum
+    /// without accumulating rounding errors.
+    ///
+    /// See [WmaAccu::recalc_wma_accu] for a description of the arguments.
+    ///
+    /// The default implementation calls [WmaAccu::recalc_wma_accu].
+    /// This is correct for exact accumulators such as integers.
+    #[inline]
+    fn recalc_wma_accu_exact(
+        self,
+        weighted_sum: Self,
+        first_value: Option<Self>,
+        input_value: Self,
+        weight: Self,
+        window_buffers: [&[T]; 2],
+    ) -> Result<(Self, Self), MovAvgError> {
+        self.recalc_wma_accu(
+            weighted_sum,
+            first_value,
+            input_value,
+            weight,
+            window_buffers,
+        )
+    }
+
+    /// Calculate the new plain sum and the new weighted sum
+    /// with compensated summation.
+    ///
+    /// `comps` are the current compensation terms of the plain sum and the weighted sum.
+    /// The compensated sums are `self + comps.0` and `weighted_sum + comps.1`.
+    ///
+    /// Returns the new plain sum, the new weighted sum
+    /// and the new compensation terms of the plain sum and the weighted sum.
+    ///
+    /// See [WmaAccu::recalc_wma_accu] for a description of the other arguments.
+    ///
+    /// The default implementation calls [WmaAccu::recalc_wma_accu]
+    /// and keeps the compensation terms unchanged.
+    /// This is correct for exact accumulators such as integers.
+    #[inline]
+    fn recalc_wma_accu_compensated(
+        self,
+        comps: (Self, Self),
+        weighted_sum: Self,
+        first_value: Option<Self>,
+        input_value: Self,
+        weight: Self,
+        window_buffers: [&[T]; 2],
+    ) -> Result<(Self, Self, Self, Self), MovAvgError> {
+        let (sum, weighted_sum) = self.recalc_wma_accu(
+            weighted_sum,
+            first_value,
+            input_value,
+            weight,
+            window_buffers,
+        )?;
+        Ok((sum, weighted_sum, comps.0, comps.1))
+    }
+}
+
+macro_rules! impl_int_wma_accu {
+    ($($t:ty),*) => {
+        $(
+            impl<T> WmaAccu<T> for $t {
+                #[inline]
+                fn recalc_wma_accu(self,
+                                   weighted_sum: Self,
+                                   first_value: Option<Self>,
+                                   input_value: Self,
+                                   weight: Self,
+                                   _window_buffers: [&[T]; 2]) -> Result<(Self, Self), MovAvgError> {
+                    // Subtract `sub` from `accu` and add `add`.
+                    // The intermediate result of one order of operations may overflow,
+                    // even if the final result fits. Therefore, try both orders.
+                    let sub_add = |accu: Self, sub: Self, add: Self| {
+                        accu.checked_sub(sub)
+                            .and_then(|accu| accu.checked_add(add))
+                            .or_else(|| accu.checked_add(add).and_then(|accu| accu.checked_sub(sub)))
+                            .ok_or(MovAvgError::AccuOverflow)
+                    };
+                    let weighted_input = weight.checked_mul(input_value)
+                        .ok_or(MovAvgError::AccuOverflow)?;
+                    let (weighted_sum, sum) = if let Some(first_value) = first_value {
+                        // Decrement all weights by one. This drops the first value.
+                        (sub_add(weighted_sum, self, weighted_input)?,
+                         sub_add(self, first_value, input_value)?)
+                    } else {
+                        (weighted_sum.checked_add(weighted_input).ok_or(MovAvgError::AccuOverflow)?,
+                         self.checked_add(input_value).ok_or(MovAvgError::AccuOverflow)?)
+                    };
+                    Ok((sum, weighted_sum))
+                }
+            }
+        )*
+    }
+}
+
+macro_rules! impl_float_wma_accu {
+    ($($t:ty),*) => {
+        $(
+            impl<T> WmaAccu<T> for $t
+            where
+                T: Num + NumCast + Copy
+            {
+                #[inline]
+                fn recalc_wma_accu(self,
+                                   weighted_sum: Self,
+                                   first_value: Option<Self>,
+                                   input_value: Self,
+                                   weight: Self,
+                                   _window_buffers: [&[T]; 2]) -> Result<(Self, Self), MovAvgError> {
+                    // Fast calculation, just like the integer variant.
+                    if let Some(first_value) = first_value {
+                        // Decrement all weights by one. This drops the first value.
+                        Ok(((self - first_value) + input_value,
+                            (weighted_sum - self) + weight * input_value))
+                    } else {
+                        Ok((self + input_value,
+                            weighted_sum + weight * input_value))
+                    }
+                }
+
+                #[inline]
+                fn recalc_wma_accu_exact(self,
+                                         _weighted_sum: Self,
+                                         _first_value: Option<Self>,
+                                         _input_value: Self,
+                                         _weight: Self,
+                                         window_buffers: [&[T]; 2]) -> Result<(Self, Self), MovAvgError> {
+                    // Recalculate the accumulators from scratch.
+                    initialize_wma_accu(window_buffers)
+                }
+
+                #[inline]
+                fn recalc_wma_accu_compensated(self,
+                                               comps: (Self, Self),
+                                               weighted_sum: Self,
+                                               first_value: Option<Self>,
+                                               input_value: Self,
+                                               weight: Self,
+                                               _window_buffers: [&[T]; 2])
+                                               -> Result<(Self, Self, Self, Self), MovAvgError> {
+                    let (mut sum, mut comp) = (self, comps.0);
+                    let (mut weighted_sum, mut weighted_comp) = (weighted_sum, comps.1);
+                    if let Some(first_value) = first_value {
+                        // Decrement all weights by one. This drops the first value.
+                        (weighted_sum, weighted_comp) = neumaier_add(weighted_sum, weighted_comp, -sum);
+                        (weighted_sum, weighted_comp) = neumaier_add(weighted_sum, weighted_comp, -comp);
+                        (sum, comp) = neumaier_add(sum, comp, -first_value);
+                    }
+                    (weighted_sum, weighted_comp) =
+                        neumaier_add(weighted_sum, weighted_comp, weight * input_value);
+                    (sum, comp) = neumaier_add(sum, comp, input_value);
+                    Ok((sum, weighted_sum, comp, weighted_comp))
+                }
+            }
+        )*
+    }
+}
+
+impl_int_wma_accu!(i8, i16, i32, i64, isize, u8, u16, u32, u64, usize);
+
+#[cfg(has_i128)]
+impl_int_wma_accu!(i128, u128);
+
+impl_float_wma_accu!(f32, f64);
+
+/// Linearly Weighted Moving Average (WMA)
+///
+/// The newest item in the window has the weight `WINDOW_SIZE`
+/// and the oldest item has the weight 1.
+/// If the window is not fully populated, yet, then the newest item has the weight [Self::len].
+///
+/// Feeding a new value is an O(1) operation for integer accumulators.
+///
+/// # Examples
+///
+/// ```
+/// use movavg::Wma;
+///
+/// let mut avg: Wma<i32, i32, 3> = Wma::new(); // window size = 3
+/// assert_eq!(avg.feed(10), 10);
+/// assert_eq!(avg.feed(20), 16); // (10*1 + 20*2) / 3
+/// assert_eq!(avg.feed(30), 23); // (10*1 + 20*2 + 30*3) / 6
+/// assert_eq!(avg.feed(40), 33); // (20*1 + 30*2 + 40*3) / 6
+/// assert_eq!(avg.get(), 33);
+/// ```
+///
+/// # Type Generics
+///
+/// `struct Wma<T, A, WINDOW_SIZE>`
+///
+/// * `T` - The type of the `feed()` input value.
+/// * `A` - The type of the internal accumulator.
+///   This type must be big enough to hold the weighted sum of the window.
+///   That is up to `WINDOW_SIZE * (WINDOW_SIZE + 1) / 2` times the biggest input value.
+/// * `WINDOW_SIZE` - The size of the sliding window.
+///   In number of fed elements.
+#[derive(Clone, Debug)]
+pub struct Wma<T, A, const WINDOW_SIZE: usize> {
+    buffer: [T; WINDOW_SIZE],
+    sum: A,
+    weighted_sum: A,
+    comp: A,
+    weighted_comp: A,
+    mode: AccuMode,
+    nr_since_resync: usize,
+    nr_items: usize,
+    index: usize,
+}
+
+impl<T, A, const WINDOW_SIZE: usize> Wma<T, A, WINDOW_SIZE>
+where
+    T: Num + NumCast + Copy,
+    A: Num + NumCast + Copy + WmaAccu<T>,
+{
+    /// Compile time check of the `WINDOW_SIZE` generic.
+    const WINDOW_SIZE_CHECK: () = assert!(WINDOW_SIZE > 0, "WINDOW_SIZE must not be zero.");
+
+    /// Construct a new Weighted Moving Average.
+    ///
+    /// The internal accumulators default to zero.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use movavg::Wma;
+    ///
+    /// let mut avg: Wma<i32, i32, 3> = Wma::new(); // window size = 3
+    /// assert_eq!(avg.feed(10), 10);
+    /// ```
+    pub fn new() -> Wma<T, A, WINDOW_SIZE> {
+        Self::new_init([T::one(); WINDOW_SIZE], 0)
+    }
+
+    /// Construct a new Weighted Moving Average from a pre-allocated buffer
+    /// and initialize its internal state.
+    ///
+    /// * `buffer` - (Partially) pre-populated window buffer. Contains the window values.
+    ///   The values are ordered from oldest to newest.
+    /// * `nr_populated` - The number of pre-populated Moving Average window elements in `buffer`.
+    ///   `nr_populated` must be less than or equal to `buffer.len()`.
+    ///   The populated values in `buffer` must begin at index 0.
+    ///   The values of unpopulated elements in `buffer` does not matter.
+    ///
+    /// # Panics
+    ///
+    /// Panics, if:
+    /// * `nr_populated` is bigger than `buffer.len()`.
+    /// * The initial accumulator calculation fails. (e.g. due to overflow).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use movavg::Wma;
+    ///
+    /// let mut avg: Wma<i32, i32, 3> = Wma::new_init([10, 20, 0], 2);
+    ///
+    /// assert_eq!(avg.get(), 16);
+    /// assert_eq!(avg.feed(30), 23);
+    /// ```
+    pub fn new_init(buffer: [T; WINDOW_SIZE], nr_populated: usize) -> Wma<T, A, WINDOW_SIZE> {
+        let () = Self::WINDOW_SIZE_CHECK;
+
+        let size = buffer.len();
+
+        let nr_items = nr_populated;
+        assert!(nr_items <= size);
+
+        let index = nr_items % size;
+
+        let (sum, weighted_sum) = initialize_wma_accu([&buffer[0..nr_items], &[]])
+            .expect("Failed to initialize the accumulator.");
+
+        Wma {
+            buffer,
+            sum,
+            weighted_sum,
+            comp: A::zero(),
+            weighted_comp: A::zero(),
+            mode: AccuMode::default(),
+            nr_since_resync: 0,
+            nr_items,
+            index,
+        }
+    }
+
+    /// Set the accumulation strategy of this instance.
+    ///
+    /// The accumulators are recalculated from the already populated window items.
+    ///
+    /// See [AccuMode] for the available strategies.
+    ///
+    /// # Panics
+    ///
+    /// Panics, if the accumulator recalculation fails. (e.g. due to overflow).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use movavg::{AccuMode, Wma};
+    ///
+    /// let mut avg: Wma<f64, f64, 2> = Wma::new().with_accu_mode(AccuMode::Compensated);
+    /// avg.feed(1e16);
+    /// avg.feed(1.0);
+    /// avg.feed(1.0);
+    /// assert_eq!(avg.feed(4.0), (1.0 + 2.0 * 4.0) / 3.0);
+    /// ```
+    pub fn with_accu_mode(mut self, mode: AccuMode) -> Self {
+        let (sum, weighted_sum) = initialize_wma_accu(self.window_buffers())
+            .expect("Failed to initialize the accumulator.");
+        self.sum = sum;
+        self.weighted_sum = weighted_sum;
+        self.comp = A::zero();
+        self.weighted_comp = A::zero();
+        self.mode = mode;
+        self.nr_since_resync = 0;
+        self
+    }
+
+    /// Get the accumulation strategy of this instance.
+    #[inline]
+    pub const fn accu_mode(&self) -> AccuMode {
+        self.mode
+    }
+
+    /// Get the populated items of the window buffer in chronological order (oldest first).
+    fn window_buffers(&self) -> [&[T]; 2] {
+        if self.nr_items >= WINDOW_SIZE {
+            let (newer, older) = self.buffer.split_at(self.index);
+            [older, newer]
+        } else {
+            [&self.buffer[0..self.nr_items], &[]]
+        }
+    }
+
+    /// Reset the Weighted Moving Average.
+    ///
+    /// This resets the accumulators and the number of accumulated items to 0,
+    /// as if this instance was re-created with [Wma::new].
+    ///
+    /// # Note
+    ///
+    /// This does not actually overwrite the buffered items in the internal buffer.
+    pub fn reset(&mut self) {
+        self.sum = A::zero();
+        self.weighted_sum = A::zero();
+        self.comp = A::zero();
+        self.weighted_comp = A::zero();
+        self.nr_since_resync = 0;
+        self.nr_items = 0;
+        self.index = 0;
+    }
+
+    /// Get the current length of the Moving Average window.
+    ///
+    /// This length is in the range of `0..WINDOW_SIZE`.
+    /// If the length is less than `WINDOW_SIZE`, then the Moving Average window
+    /// has not been fully populated, yet.
+    ///
+    /// See [Self::window_size] to get the nominal maximum length of the window.
+    #[inline]
+    pub const fn len(&self) -> usize {
+        self.nr_items
+    }
+
+    /// Check if no items have been fed into the Moving Average, yet.
+    ///
+    /// This returns true, if [Self::len] `==0`.
+    ///
+    /// This returns false, if [Self::len] `!=0`.
+    #[inline]
+    pub const fn is_empty(&self) -> bool {
+        self.nr_items == 0
+    }
+
+    /// Get the nominal size of the Moving Average window.
+    ///
+    /// This is always equal to `WINDOW_SIZE`.
+    ///
+    /// See [Self::len] to get the actual current length of the window.
+    #[inline]
+    pub const fn window_size(&self) -> usize {
+        WINDOW_SIZE
+    }
+
+    /// Get the sum of all weights for a window with `nr_items` items.
+    #[inline]
+    fn weights_sum(nr_items: usize) -> Result<A, MovAvgError> {
+        nr_items
+            .checked_mul(nr_items + 1)
+            .and_then(|w| A::from(w / 2))
+            .ok_or(MovAvgError::CastCount)
+    }
+
+    /// Try to feed a new value into the Weighted Moving Average and return the new average.
+    ///
+    /// * `value` - The new value to feed into the Weighted Moving Average.
+    ///
+    /// On success, returns `Ok(T)` with the new Weighted Moving Average result.
+    ///
+    /// Returns `Err`, if the internal accumulator overflows, or if any value conversion fails.
+    /// Value conversion does not fail, if the types are big enough to hold the values.
+    pub fn try_feed(&mut self, value: T) -> Result<T, MovAvgError> {
+        let size = self.buffer.len();
+        debug_assert!(self.nr_items <= size);
+        let window_full = self.nr_items >= size;
+
+        // Get the first element from the moving window state.
+        let first_value = if window_full {
+            Some(A::from(self.buffer[self.index]).ok_or(MovAvgError::CastInput)?)
+        } else {
+            None
+        };
+
+        let a_value = A::from(value).ok_or(MovAvgError::CastInput)?;
+
+        // Calculate the new moving window state fill state.
+        let new_nr_items = if window_full {
+            self.nr_items // Already fully populated.
+        } else {
+            self.nr_items + 1
+        };
+        let a_weight = A::from(new_nr_items).ok_or(MovAvgError::CastCount)?;
+        let a_weights_sum = Self::weights_sum(new_nr_items)?;
+
+        // Insert the new value into the moving window state.
+        // If en error happens later, orig_item has to be restored.
+        let orig_item = self.buffer[self.index];
+        self.buffer[self.index] = value;
+
+        // Recalculate the accumulators.
+        let window_buffers = if window_full {
+            let (newer, older) = self.buffer.split_at(self.index + 1);
+            [older, newer]
+        } else {
+            [&self.buffer[0..new_nr_items], &[][..]]
+        };
+        let comps = (self.comp, self.weighted_comp);
+        let new_accu = match self.mode {
+            AccuMode::Exact => self
+                .sum
+                .recalc_wma_accu_exact(
+                    self.weighted_sum,
+                    first_value,
+                    a_value,
+                    a_weight,
+                    window_buffers,
+                )
+                .map(|(sum, weighted_sum)| (sum, weighted_sum, comps.0, comps.1)),
+            AccuMode::Incremental => self
+                .sum
+                .recalc_wma_accu(
+                    self.weighted_sum,
+                    first_value,
+                    a_value,
+                    a_weight,
+                    window_buffers,
+                )
+                .map(|(sum, weighted_sum)| (sum, weighted_sum, comps.0, comps.1)),
+            AccuMode::Resync(interval) => if self.nr_since_resync + 1 >= interval {
+                self.sum.recalc_wma_accu_exact(
+                    self.weighted_sum,
+                    first_value,
+                    a_value,
+                    a_weight,
+                    window_buffers,
+                )
+            } else {
+                self.sum.recalc_wma_accu(
+                    self.weighted_sum,
+                    first_value,
+                    a_value,
+                    a_weight,
+                    window_buffers,
+                )
+            }
+            .map(|(sum, weighted_sum)| (sum, weighted_sum, comps.0, comps.1)),
+            AccuMode::Compensated => self.sum.recalc_wma_accu_compensated(
+                comps,
+                self.weighted_sum,
+                first_value,
+                a_value,
+                a_weight,
+                window_buffers,
+            ),
+        };
+        match new_accu {
+            Ok((new_sum, new_weighted_sum, new_comp, new_weighted_comp)) => {
+                // Calculate the new average.
+                match T::from((new_weighted_sum + new_weighted_comp) / a_weights_sum) {
+                    Some(avg) => {
+                        // Update the state.
+                        self.nr_items = new_nr_items;
+                        self.index = (self.index + 1) % size;
+                        self.sum = new_sum;
+                        self.weighted_sum = new_weighted_sum;
+                        self.comp = new_comp;
+                        self.weighted_comp = new_weighted_comp;
+                        if let AccuMode::Resync(interval) = self.mode {
+                            self.nr_since_resync = (self.nr_since_resync + 1) % interval.max(1);
+                        }
+
+                        // Return the end result.
+                        Ok(avg)
+                    }
+                    None => {
+                        // Restore the original moving window state.
+                        self.buffer[self.index] = orig_item;
+                        Err(MovAvgError::CastResult)
+                    }
+                }
+            }
+            Err(e) => {
+                // Restore the original moving window state.
+                self.buffer[self.index] = orig_item;
+                Err(e)
+            }
+        }
+    }
+
+    /// Feed a new value into the Weighted Moving Average and return the new average.
+    ///
+    /// * `value` - The new value to feed into the Weighted Moving Average.
+    ///
+    /// Returns the new Weighted Moving Average result.
+    ///
+    /// # Panics
+    ///
+    /// Panics, if the internal accumulator overflows, or if any value conversion fails.
+    /// Value conversion does not fail, if the types are big enough to hold the values.
+    pub fn feed(&mut self, value: T) -> T {
+        match self.try_feed(value) {
+            Ok(avg) => avg,
+            Err(e) => panic!("Wma calculation failed: {}", e),
+        }
+    }
+
+    /// Try to get the current Weighted Moving Average value.
+    /// This method does not modify the internal state.
+    ///
+    /// Returns `Err`, if the internal state is empty.
+    /// That is if no values have been fed into Wma.
+    ///
+    /// Returns `Err`, if any value conversion fails.
+    /// Value conversion does not fail, if the types are big enough to hold the values.
+    pub fn try_get(&self) -> Result<T, MovAvgError> {
+        if self.nr_items == 0 {
+            Err(MovAvgError::Empty)
+        } else {
+            let weights_sum = Self::weights_sum(self.nr_items)?;
+            T::from((self.weighted_sum + self.weighted_comp) / weights_sum)
+                .ok_or(MovAvgError::CastResult)
+        }
+    }
+
+    /// Get the current Weighted Moving Average value.
+    /// This method does not modify the internal state.
+    ///
+    /// # Panics
+    ///
+    /// Panics, if the internal state is empty.
+    /// That is if no values have been fed into Wma.
+    ///
+    /// Panics, if any value conversion fails.
+    /// Value conversion does not fail, if the types are big enough to hold the values.
+    pub fn get(&self) -> T {
+        match self.try_get() {
+            Ok(avg) => avg,
+            Err(MovAvgError::Empty) => panic!("Wma calculation failed: The Wma state is empty."),
+            Err(e) => panic!("Wma calculation failed: {}", e),
+        }
+    }
+}
+
+impl<A, T, const WINDOW_SIZE: usize> Default for Wma<T, A, WINDOW_SIZE>
+where
+    T: Num + NumCast + Copy,
+    A: Num + NumCast + Copy + WmaAccu<T>,
+{
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Reference implementation.
+    fn wma(window: &[i64]) -> i64 {
+        let weighted: i64 = window
+            .iter()
+            .enumerate()
+            .map(|(i, v)| (i as i64 + 1) * v)
+            .sum();
+        let n = window.len() as i64;
+        weighted / (n * (n + 1) / 2)
+    }
+
+    #[test]
+    fn test_i32() {
+        let mut a: Wma<i32, i32, 4> = Wma::new();
+        assert_eq!(a.feed(10), 10);
+        assert_eq!(a.feed(20), (10 + 2 * 20) / 3);
+        assert_eq!(a.feed(-2), (10 + 2 * 20 - 3 * 2) / 6);
+        assert_eq!(a.feed(100), (10 + 2 * 20 - 3 * 2 + 4 * 100) / 10);
+        assert_eq!(a.feed(111), (20 - 2 * 2 + 3 * 100 + 4 * 111) / 10);
+        assert_eq!(a.feed(-200), (-2 + 2 * 100 + 3 * 111 - 4 * 200) / 10);
+        assert_eq!(a.get(), (-2 + 2 * 100 + 3 * 111 - 4 * 200) / 10);
+    }
+
+    #[test]
+    fn test_u8() {
+        let mut a: Wma<u8, u16, 3> = Wma::new();
+        assert_eq!(a.feed(250), 250);
+        assert_eq!(a.feed(10), ((250 + 2 * 10) / 3) as u8);
+        assert_eq!(a.feed(200), ((250 + 2 * 10 + 3 * 200) / 6) as u8);
+        assert_eq!(a.feed(0), ((10 + 2 * 200) / 6) as u8);
+        assert_eq!(a.feed(0), 200 / 6);
+        assert_eq!(a.feed(0), 0);
+    }
+
+    #[test]
+    fn test_reference() {
+        let mut a: Wma<i64, i64, 7> = Wma::new();
+        let mut window = [0_i64; 7];
+        let mut value: i64 = 1;
+        for i in 0..100 {
+            value = (value * 7919 + 13) % 10007 - 5000;
+            let n = (i + 1).min(7);
+            window.copy_within(1.., 0);
+            window[6] = value;
+            assert_eq!(a.feed(value), wma(&window[7 - n..]));
+        }
+    }
+
+    #[test]
+    fn test_f64() {
+        let mut a: Wma<f64, f64, 3> = Wma::new();
+        let e = 0.000001;
+        assert!((a.feed(10.0) - 10.0).abs() < e);
+        assert!((a.feed(20.0) - (10.0 + 2.0 * 20.0) / 3.0).abs() < e);
+        assert!((a.feed(30.0) - (10.0 + 2.0 * 20.0 + 3.0 * 30.0) / 6.0).abs() < e);
+        assert!((a.feed(-40.0) - (20.0 + 2.0 * 30.0 - 3.0 * 40.0) / 6.0).abs() < e);
+        assert!((a.feed(50.0) - (30.0 - 2.0 * 40.0 + 3.0 * 50.0) / 6.0).abs() < e);
+    }
+
+    #[test]
+    fn test_accu_modes() {
+        let default: Wma<f64, f64, 2> = Wma::new();
+        if cfg!(feature = "fastfloat") {
+            assert_eq!(default.accu_mode(), AccuMode::Incremental);
+        } else {
+            assert_eq!(default.accu_mode(), AccuMode::Exact);
+        }
+
+        let mut exact: Wma<f64, f64, 2> = Wma::new().with_accu_mode(AccuMode::Exact);
+        let mut incr: Wma<f64, f64, 2> = Wma::new().with_accu_mode(AccuMode::Incremental);
+        let mut resync: Wma<f64, f64, 2> = Wma::new().with_accu_mode(AccuMode::Resync(3));
+        let mut comp: Wma<f64, f64, 2> = Wma::new().with_accu_mode(AccuMode::Compensated);
+        for value in [1e17, 1.0, 4.0] {
+            exact.feed(value);
+            incr.feed(value);
+            resync.feed(value);
+            comp.feed(value);
+        }
+        assert_eq!(exact.get(), 3.0);
+        assert_ne!(incr.get(), 3.0); // 1.0 has been lost in cancellation.
+        assert_eq!(resync.get(), 3.0); // Resynchronized in this feed.
+        assert_eq!(comp.get(), 3.0);
+
+        // Switching the mode recalculates the accumulators.
+        let mut incr = incr.with_accu_mode(AccuMode::Exact);
+        assert_eq!(incr.get(), 3.0);
+        assert_eq!(incr.feed(7.0), (4.0 + 2.0 * 7.0) / 3.0);
+
+        // Integer accumulators are always exact.
+        let mut a: Wma<i32, i32, 3> = Wma::new().with_accu_mode(AccuMode::Compensated);
+        assert_eq!(a.feed(10), 10);
+        assert_eq!(a.feed(20), (10 + 2 * 20) / 3);
+        assert_eq!(a.feed(30), (10 + 2 * 20 + 3 * 30) / 6);
+        assert_eq!(a.feed(40), (20 + 2 * 30 + 3 * 40) / 6);
+    }
+
+    #[test]
+    fn test_single() {
+        let mut a: Wma<i32, i32, 1> = Wma::new();
+        assert_eq!(a.window_size(), 1);
+        assert_eq!(a.feed(10), 10);
+        assert_eq!(a.feed(20), 20);
+        assert_eq!(a.feed(2), 2);
+    }
+
+    #[test]
+    fn test_accu_overflow() {
+        let mut a: Wma<u8, u8, 3> = Wma::new();
+        a.feed(50);
+        assert!(a.try_feed(130).is_err());
+        assert_eq!(a.len(), 1);
+        assert_eq!(a.get(), 50);
+        assert_eq!(a.feed(60), (50 + 2 * 60) / 3);
+    }
+
+    #[test]
+    fn test_accu_overflow_signed() {
+        let mut a: Wma<i8, i8, 3> = Wma::new();
+        assert_eq!(a.feed(-120), -120);
+        assert_eq!(a.feed(60), 0);
+        assert_eq!(a.feed(40), 20);
+        // The weighted sum 1 * 60 + 2 * 40 + 3 * 0 = 140 does not fit into i8.
+        assert_eq!(a.try_feed(0), Err(MovAvgError::AccuOverflow));
+        assert_eq!(a.len(), 3);
+        assert_eq!(a.get(), 20);
+        // The intermediate weighted sum overflows, but the final weighted sum fits.
+        assert_eq!(a.try_feed(-20), Ok(80 / 6)); // (60 + 2 * 40 - 3 * 20) / 6
+    }
+
+    #[test]
+    #[should_panic(expected = "Accumulator type add overflow")]
+    fn test_accu_overflow_panic() {
+        let mut a: Wma<u8, u8, 3> = Wma::new();
+        a.feed(50);
+        a.feed(200); // this panics
+    }
+
+    #[test]
+    fn test_init() {
+        let mut a: Wma<i32, i32, 3> = Wma::new_init([10, 20, 99], 2);
+        assert_eq!(a.len(), 2);
+        assert_eq!(a.get(), (10 + 2 * 20) / 3);
+        assert_eq!(a.feed(30), (10 + 2 * 20 + 3 * 30) / 6);
+        assert_eq!(a.feed(40), (20 + 2 * 30 + 3 * 40) / 6);
+
+        let mut a: Wma<i32, i32, 3> = Wma::new_init([10, 20, 30], 3);
+        assert_eq!(a.len(), 3);
+        assert_eq!(a.get(), (10 + 2 * 20 + 3 * 30) / 6);
+        assert_eq!(a.feed(40), (20 + 2 * 30 + 3 * 40) / 6);
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut a: Wma<i32, i32, 3> = Wma::new();
+        assert_eq!(a.feed(10), 10);
+        assert_eq!(a.feed(20), (10 + 2 * 20) / 3);
+        a.reset();
+        assert!(a.is_empty());
+        assert!(a.try_get().is_err());
+        assert_eq!(a.feed(30), 30);
+        assert_eq!(a.feed(60), (30 + 2 * 60) / 3);
+    }
+
+    #[test]
+    #[should_panic(expected = "The Wma state is empty")]
+    fn test_get_empty_panic() {
+        let a: Wma<i32, i32, 3> = Wma::new();
+        assert_eq!(a.get(), 42); // this panics
+    }
+}
+
+// vim: ts=4 sw=4 expandtab
@@ -0,0 +1,35 @@
+// -*- coding: utf-8 -*-
+//
+// Copyright 2026 Michael Büsch <m@bues.ch>
+//
+// Licensed under the Apache License version 2.0
+// or the MIT license, at your option.
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//
+
+extern crate movavg;
+use movavg::Ema;
+
+#[test]
+fn test_ema() {
+    // Integers
+    let mut avg: Ema<i32, i64> = Ema::new(1, 2); // alpha = 1/2
+    assert_eq!(avg.feed(10), 10);
+    assert_eq!(avg.feed(20), 15);
+    assert_eq!(avg.feed(30), 22);
+    assert_eq!(avg.get(), 22);
+
+    // Floats
+    let mut avg: Ema<f64, f64> = Ema::new(1, 2);
+    assert_eq!(avg.feed(10.0), 10.0);
+    assert_eq!(avg.feed(20.0), 15.0);
+    assert_eq!(avg.feed(30.0), 22.5);
+    assert_eq!(avg.get(), 22.5);
+
+    // Span
+    let mut avg: Ema<f64, f64> = Ema::new_span(3); // alpha = 2 / (3 + 1)
+    assert_eq!(avg.feed(10.0), 10.0);
+    assert_eq!(avg.feed(20.0), 15.0);
+}
+
+// vim: ts=4 sw=4 expandtab
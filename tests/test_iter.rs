@@ -0,0 +1,35 @@
+// -*- coding: utf-8 -*-
+//
+// Copyright 2026 Michael Büsch <m@bues.ch>
+//
+// Licensed under the Apache License version 2.0
+// or the MIT license, at your option.
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//
+
+extern crate movavg;
+use movavg::{MovAvg, MovAvgError, MovAvgIterExt};
+
+#[test]
+fn test_iter() {
+    let samples = [10_i16, 20, 30, 40, 50, 60];
+
+    let mut avg: MovAvg<i16, i32, 4> = MovAvg::new();
+    let mut it = samples.iter().copied().moving_avg::<i32, 4>();
+    for value in samples {
+        assert_eq!(it.next(), Some(avg.feed(value)));
+    }
+    assert_eq!(it.next(), None);
+
+    let mut full = samples.iter().copied().moving_avg::<i32, 4>().skip_warmup();
+    assert_eq!(full.next(), Some(25));
+    assert_eq!(full.next(), Some(35));
+    assert_eq!(full.next(), Some(45));
+    assert_eq!(full.next(), None);
+
+    let res: Result<Vec<i16>, MovAvgError> =
+        samples.iter().copied().try_moving_avg::<i16, 4>().collect();
+    assert_eq!(res, Ok(vec![10, 15, 20, 25, 35, 45]));
+}
+
+// vim: ts=4 sw=4 expandtab
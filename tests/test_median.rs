@@ -0,0 +1,29 @@
+// -*- coding: utf-8 -*-
+//
+// Copyright 2026 Michael Büsch <m@bues.ch>
+//
+// Licensed under the Apache License version 2.0
+// or the MIT license, at your option.
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//
+
+extern crate movavg;
+use movavg::{MedianPolicy, MovMedian};
+
+#[test]
+fn test_median() {
+    let mut med: MovMedian<u16, 5> = MovMedian::new();
+    let mut out = [0; 8];
+    for (i, value) in [100, 101, 4095, 99, 0, 102, 100, 98].iter().enumerate() {
+        out[i] = med.feed(*value);
+    }
+    assert_eq!(out, [100, 100, 101, 100, 100, 101, 100, 99]);
+
+    let mut med: MovMedian<f32, 4> = MovMedian::new().with_policy(MedianPolicy::Upper);
+    for value in [1.0, 4.0, 3.0, 2.0] {
+        med.feed(value);
+    }
+    assert_eq!(med.get(), 3.0);
+}
+
+// vim: ts=4 sw=4 expandtab
@@ -0,0 +1,29 @@
+// -*- coding: utf-8 -*-
+//
+// Copyright 2026 Michael Büsch <m@bues.ch>
+//
+// Licensed under the Apache License version 2.0
+// or the MIT license, at your option.
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//
+
+extern crate movavg;
+use movavg::{MovAvg, MovMinMax};
+
+#[test]
+fn test_minmax() {
+    let mut avg: MovAvg<u16, u32, 4> = MovAvg::new();
+    let mut mm: MovMinMax<u16, 4> = MovMinMax::new();
+    for value in [100, 110, 900, 105, 95, 100] {
+        avg.feed(value);
+        mm.feed(value);
+    }
+    assert_eq!(avg.get(), 300);
+    assert_eq!(mm.len(), avg.len());
+    assert_eq!(mm.window_size(), avg.window_size());
+    assert_eq!(mm.min(), Some(95));
+    assert_eq!(mm.max(), Some(900));
+    assert_eq!(mm.range(), Some(805));
+}
+
+// vim: ts=4 sw=4 expandtab
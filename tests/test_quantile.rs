@@ -0,0 +1,27 @@
+// -*- coding: utf-8 -*-
+//
+// Copyright 2026 Michael Büsch <m@bues.ch>
+//
+// Licensed under the Apache License version 2.0
+// or the MIT license, at your option.
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//
+
+extern crate movavg;
+use movavg::{MovQuantile, QuantileMethod};
+
+#[test]
+fn test_quantile() {
+    let mut lat: MovQuantile<u16, 10> = MovQuantile::new().with_method(QuantileMethod::Nearest);
+    for value in [5, 7, 6, 5, 900, 8, 6, 5, 7, 6, 5, 6] {
+        lat.feed(value);
+    }
+    // Sorted window: 5, 5, 5, 6, 6, 6, 6, 7, 8, 900
+    assert_eq!(lat.len(), 10);
+    assert_eq!(lat.quantile(0.5), 6);
+    assert_eq!(lat.quantile(0.9), 8);
+    assert_eq!(lat.quantile(0.99), 900);
+    assert_eq!(lat.quantile(0.0), 5);
+}
+
+// vim: ts=4 sw=4 expandtab
@@ -0,0 +1,35 @@
+// -*- coding: utf-8 -*-
+//
+// Copyright 2026 Michael Büsch <m@bues.ch>
+//
+// Licensed under the Apache License version 2.0
+// or the MIT license, at your option.
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//
+
+#![cfg(feature = "serde")]
+
+extern crate movavg;
+use movavg::{AccuMode, MovAvg};
+
+#[test]
+fn test_serde() {
+    let mut avg: MovAvg<f64, f64, 5> = MovAvg::new().with_accu_mode(AccuMode::Compensated);
+    for i in 0..12 {
+        avg.feed(i as f64 * 0.7);
+    }
+
+    let json = serde_json::to_string(&avg).unwrap();
+    let mut restored: MovAvg<f64, f64, 5> = serde_json::from_str(&json).unwrap();
+    assert_eq!(restored.accu_mode(), AccuMode::Compensated);
+    assert_eq!(restored.len(), 5);
+    assert!(restored.iter().eq(avg.iter()));
+    for i in 0..12 {
+        assert_eq!(restored.feed(i as f64), avg.feed(i as f64));
+    }
+
+    // The window size is part of the type.
+    assert!(serde_json::from_str::<MovAvg<f64, f64, 4>>(&json).is_err());
+}
+
+// vim: ts=4 sw=4 expandtab
@@ -8,7 +8,7 @@
 //
 
 extern crate movavg;
-use movavg::MovAvg;
+use movavg::{MovAvg, MovAvgAccu, MovAvgError};
 
 #[test]
 fn test_sma() {
@@ -34,4 +34,26 @@ fn test_sma() {
     assert_eq!(avg.feed(100), 100); // This would overflow an i8 accumulator
 }
 
+/// An accumulator outside of this crate that only implements the required method.
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct ExternAccu(i64);
+
+impl MovAvgAccu<i32> for ExternAccu {
+    fn recalc_accu(
+        self,
+        first_value: Self,
+        input_value: Self,
+        _window_buffer: &[i32],
+    ) -> Result<Self, MovAvgError> {
+        Ok(ExternAccu(self.0 - first_value.0 + input_value.0))
+    }
+}
+
+#[test]
+fn test_extern_accu() {
+    let accu = ExternAccu(10).recalc_accu(ExternAccu(3), ExternAccu(5), &[5]);
+    assert_eq!(accu, Ok(ExternAccu(12)));
+    assert!(!ExternAccu::is_float());
+}
+
 // vim: ts=4 sw=4 expandtab
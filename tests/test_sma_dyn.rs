@@ -0,0 +1,36 @@
+// -*- coding: utf-8 -*-
+//
+// Copyright 2026 Michael Büsch <m@bues.ch>
+//
+// Licensed under the Apache License version 2.0
+// or the MIT license, at your option.
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//
+
+#![cfg(feature = "alloc")]
+
+extern crate movavg;
+use movavg::DynMovAvg;
+
+#[test]
+fn test_sma_dyn() {
+    let window_size = 3;
+
+    // Integers
+    let mut avg: DynMovAvg<i32, i32> = DynMovAvg::new(window_size);
+    assert_eq!(avg.feed(10), 10);
+    assert_eq!(avg.feed(20), 15);
+    assert_eq!(avg.feed(30), 20);
+    assert_eq!(avg.feed(40), 30);
+    assert_eq!(avg.get(), 30);
+
+    // Floats
+    let mut avg: DynMovAvg<f64, f64> = DynMovAvg::new(window_size);
+    assert_eq!(avg.feed(10.0), 10.0);
+    assert_eq!(avg.feed(20.0), 15.0);
+    assert_eq!(avg.feed(30.0), 20.0);
+    assert_eq!(avg.feed(40.0), 30.0);
+    assert_eq!(avg.get(), 30.0);
+}
+
+// vim: ts=4 sw=4 expandtab
@@ -0,0 +1,34 @@
+// -*- coding: utf-8 -*-
+//
+// Copyright 2026 Michael Büsch <m@bues.ch>
+//
+// Licensed under the Apache License version 2.0
+// or the MIT license, at your option.
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//
+
+extern crate movavg;
+use movavg::MovAvgRef;
+
+#[test]
+fn test_sma_ref() {
+    // Integers
+    let mut buf = [0; 3]; // window size = 3
+    let mut avg: MovAvgRef<i32, i32> = MovAvgRef::new(&mut buf);
+    assert_eq!(avg.feed(10), 10);
+    assert_eq!(avg.feed(20), 15);
+    assert_eq!(avg.feed(30), 20);
+    assert_eq!(avg.feed(40), 30);
+    assert_eq!(avg.get(), 30);
+
+    // Floats
+    let mut buf = [0.0; 3];
+    let mut avg: MovAvgRef<f64, f64> = MovAvgRef::new(&mut buf);
+    assert_eq!(avg.feed(10.0), 10.0);
+    assert_eq!(avg.feed(20.0), 15.0);
+    assert_eq!(avg.feed(30.0), 20.0);
+    assert_eq!(avg.feed(40.0), 30.0);
+    assert_eq!(avg.get(), 30.0);
+}
+
+// vim: ts=4 sw=4 expandtab
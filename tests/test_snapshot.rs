@@ -0,0 +1,38 @@
+// -*- coding: utf-8 -*-
+//
+// Copyright 2026 Michael Büsch <m@bues.ch>
+//
+// Licensed under the Apache License version 2.0
+// or the MIT license, at your option.
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//
+
+extern crate movavg;
+use movavg::{MovAvg, MovAvgError, Rounding};
+
+#[test]
+fn test_snapshot() {
+    let mut avg: MovAvg<u16, u32, 10> = MovAvg::new().with_rounding(Rounding::HalfEven);
+    let mut eeprom = [0xFF_u8; MovAvg::<u16, u32, 10>::MAX_SNAPSHOT_LEN];
+
+    for i in 0..25 {
+        avg.feed(i * 100);
+        let len = avg.to_bytes(&mut eeprom).unwrap();
+        assert_eq!(len, avg.snapshot_len());
+
+        // Simulated reset
+        let restored: MovAvg<u16, u32, 10> = MovAvg::from_bytes(&eeprom).unwrap();
+        assert_eq!(restored.len(), avg.len());
+        assert_eq!(restored.rounding(), Rounding::HalfEven);
+        assert!(restored.iter().eq(avg.iter()));
+        assert_eq!(restored.clone().feed(7), avg.clone().feed(7));
+    }
+
+    eeprom[40] ^= 0x80;
+    assert_eq!(
+        MovAvg::<u16, u32, 10>::from_bytes(&eeprom).unwrap_err(),
+        MovAvgError::SnapshotChecksum
+    );
+}
+
+// vim: ts=4 sw=4 expandtab
@@ -0,0 +1,38 @@
+// -*- coding: utf-8 -*-
+//
+// Copyright 2026 Michael Büsch <m@bues.ch>
+//
+// Licensed under the Apache License version 2.0
+// or the MIT license, at your option.
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//
+
+#![cfg(feature = "futures")]
+
+extern crate movavg;
+use futures::{executor::block_on_stream, stream};
+use movavg::{MovAvg, MovAvgStreamExt};
+
+#[test]
+fn test_stream() {
+    let samples = [100_u16, 200, 300, 400, 500, 600, 700];
+
+    let mut avg: MovAvg<u16, u32, 3> = MovAvg::new();
+    let s = stream::iter(samples).moving_avg::<u32, 3>();
+    assert!(block_on_stream(s).eq(samples.iter().map(|v| avg.feed(*v))));
+
+    let s = stream::iter(samples).moving_avg::<u32, 3>().decimate(3);
+    assert!(block_on_stream(s).eq([200, 500]));
+
+    // Streams that are not Unpin.
+    let s = Box::pin(stream::unfold(0_u16, |v| async move {
+        if v < 4 {
+            Some((v * 10, v + 1))
+        } else {
+            None
+        }
+    }));
+    assert!(block_on_stream(s.moving_avg::<u16, 2>()).eq([0, 5, 15, 25]));
+}
+
+// vim: ts=4 sw=4 expandtab
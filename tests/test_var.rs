@@ -0,0 +1,38 @@
+// -*- coding: utf-8 -*-
+//
+// Copyright 2026 Michael Büsch <m@bues.ch>
+//
+// Licensed under the Apache License version 2.0
+// or the MIT license, at your option.
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//
+
+extern crate movavg;
+use movavg::MovVar;
+
+#[test]
+fn test_var() {
+    // Integers
+    let mut var: MovVar<i32, i64, 8> = MovVar::new(); // window size = 8
+    for value in [2, 4, 4, 4, 5, 5, 7, 9] {
+        var.feed(value);
+    }
+    assert_eq!(var.get(), 5);
+    assert_eq!(var.variance(), 4);
+    assert_eq!(var.std_dev(), 2);
+}
+
+#[test]
+#[cfg(any(feature = "std", feature = "libm"))]
+fn test_var_float() {
+    let mut var: MovVar<f64, f64, 8> = MovVar::new();
+    for value in [2.0, 4.0, 4.0, 4.0, 5.0, 5.0, 7.0, 9.0] {
+        var.feed(value);
+    }
+    assert_eq!(var.get(), 5.0);
+    assert_eq!(var.variance(), 4.0);
+    assert_eq!(var.std_dev(), 2.0);
+    assert!((var.sample_variance() - 32.0 / 7.0).abs() < 1e-9);
+}
+
+// vim: ts=4 sw=4 expandtab
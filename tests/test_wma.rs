@@ -0,0 +1,32 @@
+// -*- coding: utf-8 -*-
+//
+// Copyright 2026 Michael Büsch <m@bues.ch>
+//
+// Licensed under the Apache License version 2.0
+// or the MIT license, at your option.
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//
+
+extern crate movavg;
+use movavg::Wma;
+
+#[test]
+fn test_wma() {
+    // Integers
+    let mut avg: Wma<i32, i32, 3> = Wma::new(); // window size = 3
+    assert_eq!(avg.feed(10), 10);
+    assert_eq!(avg.feed(20), 16);
+    assert_eq!(avg.feed(30), 23);
+    assert_eq!(avg.feed(40), 33);
+    assert_eq!(avg.get(), 33);
+
+    // Floats
+    let mut avg: Wma<f64, f64, 3> = Wma::new();
+    assert_eq!(avg.feed(10.0), 10.0);
+    assert_eq!(avg.feed(40.0), 30.0);
+    assert_eq!(avg.feed(40.0), 35.0);
+    assert_eq!(avg.feed(10.0), 25.0);
+    assert_eq!(avg.get(), 25.0);
+}
+
+// vim: ts=4 sw=4 expandtab